@@ -0,0 +1,43 @@
+//! Aggregations and inspection over exported spans.
+//!
+//! These helpers operate on [`SpanData`] as produced by an OpenTelemetry
+//! exporter, for trace-level rollups, snapshot tests, and offline analysis.
+//! Available with the `testing` feature.
+
+use openinference_semantic_conventions::attributes;
+use opentelemetry::Value;
+use opentelemetry_sdk::trace::SpanData;
+
+/// Sum `llm.cost.total` across a set of exported spans.
+///
+/// Spans without a cost, or with a non-numeric cost, contribute nothing.
+pub fn sum_cost(spans: &[SpanData]) -> f64 {
+    spans
+        .iter()
+        .filter_map(|span| {
+            span.attributes
+                .iter()
+                .find(|kv| kv.key == attributes::llm::cost::TOTAL)
+        })
+        .filter_map(|kv| match kv.value {
+            Value::F64(cost) => Some(cost),
+            Value::I64(cost) => Some(cost as f64),
+            _ => None,
+        })
+        .sum()
+}
+
+/// All attributes of an exported span as `(key, value)` strings, sorted.
+///
+/// Includes both `span!`-declared fields and keys set through
+/// `set_attribute`, so the result can be compared against a snapshot.
+/// Non-string values are rendered with their OTel display form.
+pub fn flatten_attributes(span: &SpanData) -> Vec<(String, String)> {
+    let mut attrs: Vec<(String, String)> = span
+        .attributes
+        .iter()
+        .map(|kv| (kv.key.to_string(), kv.value.to_string()))
+        .collect();
+    attrs.sort();
+    attrs
+}
@@ -4,6 +4,7 @@
 //! supporting environment variable loading, programmatic builder construction, and
 //! compound hide logic (e.g., `hide_inputs` implies hiding input messages, text, and images).
 
+use std::collections::HashMap;
 use std::env;
 
 /// Placeholder value used when content is redacted due to privacy configuration.
@@ -23,10 +24,38 @@ const ENV_HIDE_EMBEDDINGS_VECTORS: &str = "OPENINFERENCE_HIDE_EMBEDDINGS_VECTORS
 const ENV_HIDE_EMBEDDINGS_TEXT: &str = "OPENINFERENCE_HIDE_EMBEDDINGS_TEXT";
 const ENV_HIDE_PROMPTS: &str = "OPENINFERENCE_HIDE_PROMPTS";
 const ENV_HIDE_CHOICES: &str = "OPENINFERENCE_HIDE_CHOICES";
+const ENV_HIDE_AGENT_STATE: &str = "OPENINFERENCE_HIDE_AGENT_STATE";
+const ENV_HIDE_TOOL_PARAMETERS: &str = "OPENINFERENCE_HIDE_TOOL_PARAMETERS";
+const ENV_HIDE_OUTPUT_SCHEMA: &str = "OPENINFERENCE_HIDE_OUTPUT_SCHEMA";
 const ENV_BASE64_IMAGE_MAX_LENGTH: &str = "OPENINFERENCE_BASE64_IMAGE_MAX_LENGTH";
 
 const DEFAULT_BASE64_IMAGE_MAX_LENGTH: usize = 32_000;
 
+/// How embedding vectors are encoded when recorded on a span.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum EmbeddingVectorEncoding {
+    /// Record the vector as an array of floats (the OpenInference default).
+    #[default]
+    Float,
+    /// Record the vector as a base64 string of little-endian `f32` bytes,
+    /// which is considerably smaller for large vectors.
+    Base64,
+}
+
+/// Shared defaults applied to every [`LlmSpanBuilder`](crate::LlmSpanBuilder)
+/// that uses a config carrying them.
+///
+/// Values fill in builder fields that haven't been set explicitly when the
+/// span is built, so a per-call setter always wins.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LlmDefaults {
+    pub provider: Option<String>,
+    pub system: Option<String>,
+    pub temperature: Option<f64>,
+    pub top_p: Option<f64>,
+    pub max_tokens: Option<i64>,
+}
+
 /// Controls the observability level of OpenInference tracing.
 ///
 /// `TraceConfig` lets you hide sensitive information from being recorded in spans
@@ -67,10 +96,65 @@ pub struct TraceConfig {
     pub hide_embeddings_text: bool,
     pub hide_prompts: bool,
     pub hide_choices: bool,
+    pub hide_agent_state: bool,
+    pub hide_tool_parameters: bool,
+    /// Hide `llm.output_schema`. Schemas are configuration rather than user
+    /// content, so no other hide flag affects them.
+    pub hide_output_schema: bool,
     pub base64_image_max_length: usize,
     /// Whether to also emit OTel GenAI semantic convention attributes.
     /// Carried forward from the original SpanConfig.
     pub emit_gen_ai_attributes: bool,
+    /// Whether builders, decorators, and recorders emit OpenInference
+    /// attributes (`llm.*`, `input.*`, ...). When false, only
+    /// `openinference.span.kind`, span markers such as `openinference.replay`,
+    /// and the OTel GenAI attributes are emitted.
+    pub emit_openinference_attributes: bool,
+    /// How embedding vectors are encoded when recorded.
+    pub embedding_vector_encoding: EmbeddingVectorEncoding,
+    /// Omit attributes whose value is an empty string instead of recording them.
+    pub skip_empty_values: bool,
+    /// Emit message content as span events (`gen_ai.user.prompt`,
+    /// `gen_ai.assistant.response`, ...) instead of indexed attributes.
+    pub content_as_events: bool,
+    /// Also record LLM input messages as one role-prefixed `input.value`
+    /// (e.g., `"system: ...\nuser: ..."`), for backends that don't read the
+    /// indexed message attributes. An explicit input value takes precedence.
+    pub flatten_messages_to_input: bool,
+    /// Record `tool_calls` as the finish reason (`llm.finish_reason` and
+    /// `gen_ai.response.finish_reasons`) when the first output tool call is
+    /// recorded. Enable only for providers that report no finish reason of
+    /// their own; recording one as well writes the key twice.
+    pub default_tool_calls_finish_reason: bool,
+    /// Attribute keys to rename on emission (e.g., `llm.model_name` -> `model`)
+    /// for backends that expect different names. Applies to every attribute
+    /// written by builders, decorators, and recorders.
+    pub key_overrides: HashMap<String, String>,
+    /// Prefix span names with the span kind (e.g., `"LLM: gpt-4"`), for
+    /// backends that only display span names.
+    pub include_kind_in_name: bool,
+    /// Emit `openinference.span.kind` in lowercase (e.g., `llm`), for
+    /// backends that expect it. `SpanKind::as_str` is unaffected.
+    pub lowercase_span_kind: bool,
+    /// Name of the component emitting spans, recorded as
+    /// `openinference.component` on every span so that several instrumented
+    /// components in one process can be told apart.
+    pub component_name: Option<String>,
+    /// Maximum number of retrieval documents recorded per span; the rest are
+    /// dropped and `retrieval.documents_truncated` is set. `None` records all.
+    pub max_documents_recorded: Option<usize>,
+    /// Prefix prepended to OpenInference attribute keys (e.g., `"oi."` turns
+    /// `llm.model_name` into `oi.llm.model_name`), for coexisting with another
+    /// consumer of the same keys. OTel GenAI keys and the `openinference.*`
+    /// markers (span kind, component, ...) are never prefixed, so backends can
+    /// still classify spans. Applies to builders, decorators, and
+    /// post-creation recorders alike.
+    pub attribute_prefix: Option<String>,
+    /// Level at which builders create spans. Defaults to `INFO`; lower it to
+    /// `DEBUG` to keep LLM spans out of INFO-filtered subscribers.
+    pub span_level: tracing::Level,
+    /// Defaults merged into LLM span builders when this config is applied.
+    pub llm_defaults: LlmDefaults,
 }
 
 impl Default for TraceConfig {
@@ -90,8 +174,25 @@ impl Default for TraceConfig {
             hide_embeddings_text: false,
             hide_prompts: false,
             hide_choices: false,
+            hide_agent_state: false,
+            hide_tool_parameters: false,
+            hide_output_schema: false,
             base64_image_max_length: DEFAULT_BASE64_IMAGE_MAX_LENGTH,
             emit_gen_ai_attributes: true,
+            emit_openinference_attributes: true,
+            embedding_vector_encoding: EmbeddingVectorEncoding::Float,
+            skip_empty_values: true,
+            content_as_events: false,
+            flatten_messages_to_input: false,
+            default_tool_calls_finish_reason: false,
+            key_overrides: HashMap::new(),
+            include_kind_in_name: false,
+            lowercase_span_kind: false,
+            component_name: None,
+            max_documents_recorded: None,
+            attribute_prefix: None,
+            span_level: tracing::Level::INFO,
+            llm_defaults: LlmDefaults::default(),
         }
     }
 }
@@ -107,28 +208,59 @@ impl TraceConfig {
     /// Boolean env vars accept `true`/`false` and `1`/`0` (case-insensitive).
     /// Invalid values are silently ignored and the default is used.
     pub fn from_env() -> Self {
+        let vars = env::vars_os()
+            .filter_map(|(key, value)| Some((key.into_string().ok()?, value.into_string().ok()?)))
+            .collect();
+        Self::from_map(&vars)
+    }
+
+    /// Load configuration from a map of variables, as [`from_env`](Self::from_env)
+    /// does from the process environment.
+    ///
+    /// Useful in tests and when embedding, since it never touches process-wide
+    /// env state.
+    pub fn from_map(vars: &HashMap<String, String>) -> Self {
         Self {
-            hide_inputs: parse_bool_env(ENV_HIDE_INPUTS, false),
-            hide_outputs: parse_bool_env(ENV_HIDE_OUTPUTS, false),
-            hide_input_messages: parse_bool_env(ENV_HIDE_INPUT_MESSAGES, false),
-            hide_output_messages: parse_bool_env(ENV_HIDE_OUTPUT_MESSAGES, false),
-            hide_input_images: parse_bool_env(ENV_HIDE_INPUT_IMAGES, false),
-            hide_input_text: parse_bool_env(ENV_HIDE_INPUT_TEXT, false),
-            hide_output_text: parse_bool_env(ENV_HIDE_OUTPUT_TEXT, false),
-            hide_llm_invocation_parameters: parse_bool_env(
+            hide_inputs: parse_bool(vars, ENV_HIDE_INPUTS, false),
+            hide_outputs: parse_bool(vars, ENV_HIDE_OUTPUTS, false),
+            hide_input_messages: parse_bool(vars, ENV_HIDE_INPUT_MESSAGES, false),
+            hide_output_messages: parse_bool(vars, ENV_HIDE_OUTPUT_MESSAGES, false),
+            hide_input_images: parse_bool(vars, ENV_HIDE_INPUT_IMAGES, false),
+            hide_input_text: parse_bool(vars, ENV_HIDE_INPUT_TEXT, false),
+            hide_output_text: parse_bool(vars, ENV_HIDE_OUTPUT_TEXT, false),
+            hide_llm_invocation_parameters: parse_bool(
+                vars,
                 ENV_HIDE_LLM_INVOCATION_PARAMETERS,
                 false,
             ),
-            hide_embedding_vectors: parse_bool_env(ENV_HIDE_EMBEDDING_VECTORS, false),
-            hide_embeddings_vectors: parse_bool_env(ENV_HIDE_EMBEDDINGS_VECTORS, false),
-            hide_embeddings_text: parse_bool_env(ENV_HIDE_EMBEDDINGS_TEXT, false),
-            hide_prompts: parse_bool_env(ENV_HIDE_PROMPTS, false),
-            hide_choices: parse_bool_env(ENV_HIDE_CHOICES, false),
-            base64_image_max_length: parse_usize_env(
+            hide_embedding_vectors: parse_bool(vars, ENV_HIDE_EMBEDDING_VECTORS, false),
+            hide_embeddings_vectors: parse_bool(vars, ENV_HIDE_EMBEDDINGS_VECTORS, false),
+            hide_embeddings_text: parse_bool(vars, ENV_HIDE_EMBEDDINGS_TEXT, false),
+            hide_prompts: parse_bool(vars, ENV_HIDE_PROMPTS, false),
+            hide_choices: parse_bool(vars, ENV_HIDE_CHOICES, false),
+            hide_agent_state: parse_bool(vars, ENV_HIDE_AGENT_STATE, false),
+            hide_tool_parameters: parse_bool(vars, ENV_HIDE_TOOL_PARAMETERS, false),
+            hide_output_schema: parse_bool(vars, ENV_HIDE_OUTPUT_SCHEMA, false),
+            base64_image_max_length: parse_usize(
+                vars,
                 ENV_BASE64_IMAGE_MAX_LENGTH,
                 DEFAULT_BASE64_IMAGE_MAX_LENGTH,
             ),
             emit_gen_ai_attributes: true,
+            emit_openinference_attributes: true,
+            embedding_vector_encoding: EmbeddingVectorEncoding::Float,
+            skip_empty_values: true,
+            content_as_events: false,
+            flatten_messages_to_input: false,
+            default_tool_calls_finish_reason: false,
+            key_overrides: HashMap::new(),
+            include_kind_in_name: false,
+            lowercase_span_kind: false,
+            component_name: None,
+            max_documents_recorded: None,
+            attribute_prefix: None,
+            span_level: tracing::Level::INFO,
+            llm_defaults: LlmDefaults::default(),
         }
     }
 
@@ -192,6 +324,21 @@ impl TraceConfig {
     pub fn should_hide_choices(&self) -> bool {
         self.hide_outputs || self.hide_choices
     }
+
+    /// Whether agent goal, plan, and state should be hidden.
+    ///
+    /// True if `hide_outputs` or `hide_agent_state` is set.
+    pub fn should_hide_agent_state(&self) -> bool {
+        self.hide_outputs || self.hide_agent_state
+    }
+
+    /// Whether tool parameters should be hidden.
+    ///
+    /// True if input text is hidden (see [`should_hide_input_text`](Self::should_hide_input_text))
+    /// or `hide_tool_parameters` is set.
+    pub fn should_hide_tool_parameters(&self) -> bool {
+        self.should_hide_input_text() || self.hide_tool_parameters
+    }
 }
 
 // =============================================================================
@@ -217,8 +364,25 @@ pub struct TraceConfigBuilder {
     hide_embeddings_text: Option<bool>,
     hide_prompts: Option<bool>,
     hide_choices: Option<bool>,
+    hide_agent_state: Option<bool>,
+    hide_tool_parameters: Option<bool>,
+    hide_output_schema: Option<bool>,
     base64_image_max_length: Option<usize>,
     emit_gen_ai_attributes: Option<bool>,
+    emit_openinference_attributes: Option<bool>,
+    embedding_vector_encoding: Option<EmbeddingVectorEncoding>,
+    skip_empty_values: Option<bool>,
+    content_as_events: Option<bool>,
+    flatten_messages_to_input: Option<bool>,
+    default_tool_calls_finish_reason: Option<bool>,
+    key_overrides: HashMap<String, String>,
+    include_kind_in_name: Option<bool>,
+    lowercase_span_kind: Option<bool>,
+    component_name: Option<String>,
+    max_documents_recorded: Option<usize>,
+    attribute_prefix: Option<String>,
+    span_level: Option<tracing::Level>,
+    llm_defaults: Option<LlmDefaults>,
 }
 
 macro_rules! builder_setter {
@@ -250,8 +414,60 @@ impl TraceConfigBuilder {
     builder_setter!(hide_embeddings_text, bool);
     builder_setter!(hide_prompts, bool);
     builder_setter!(hide_choices, bool);
+    builder_setter!(hide_agent_state, bool);
+    builder_setter!(hide_tool_parameters, bool);
+    builder_setter!(hide_output_schema, bool);
     builder_setter!(base64_image_max_length, usize);
     builder_setter!(emit_gen_ai_attributes, bool);
+    builder_setter!(emit_openinference_attributes, bool);
+    builder_setter!(skip_empty_values, bool);
+    builder_setter!(content_as_events, bool);
+    builder_setter!(flatten_messages_to_input, bool);
+    builder_setter!(default_tool_calls_finish_reason, bool);
+    builder_setter!(include_kind_in_name, bool);
+    builder_setter!(lowercase_span_kind, bool);
+
+    /// Set how embedding vectors are encoded when recorded.
+    pub fn embedding_vector_encoding(mut self, encoding: EmbeddingVectorEncoding) -> Self {
+        self.embedding_vector_encoding = Some(encoding);
+        self
+    }
+
+    /// Emit the attribute `from` under the name `to` instead.
+    pub fn key_override(mut self, from: impl Into<String>, to: impl Into<String>) -> Self {
+        self.key_overrides.insert(from.into(), to.into());
+        self
+    }
+
+    /// Label every span with `openinference.component`.
+    pub fn component_name(mut self, name: impl Into<String>) -> Self {
+        self.component_name = Some(name.into());
+        self
+    }
+
+    /// Record at most `max` retrieval documents per span.
+    pub fn max_documents_recorded(mut self, max: usize) -> Self {
+        self.max_documents_recorded = Some(max);
+        self
+    }
+
+    /// Prepend `prefix` to every OpenInference attribute key.
+    pub fn attribute_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.attribute_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Create spans at `level` instead of `INFO`.
+    pub fn span_level(mut self, level: tracing::Level) -> Self {
+        self.span_level = Some(level);
+        self
+    }
+
+    /// Apply `defaults` to every LLM span builder using this config.
+    pub fn llm_defaults(mut self, defaults: LlmDefaults) -> Self {
+        self.llm_defaults = Some(defaults);
+        self
+    }
 
     /// Build the [`TraceConfig`].
     ///
@@ -283,12 +499,41 @@ impl TraceConfigBuilder {
                 .unwrap_or(env.hide_embeddings_text),
             hide_prompts: self.hide_prompts.unwrap_or(env.hide_prompts),
             hide_choices: self.hide_choices.unwrap_or(env.hide_choices),
+            hide_agent_state: self.hide_agent_state.unwrap_or(env.hide_agent_state),
+            hide_tool_parameters: self
+                .hide_tool_parameters
+                .unwrap_or(env.hide_tool_parameters),
+            hide_output_schema: self.hide_output_schema.unwrap_or(env.hide_output_schema),
             base64_image_max_length: self
                 .base64_image_max_length
                 .unwrap_or(env.base64_image_max_length),
             emit_gen_ai_attributes: self
                 .emit_gen_ai_attributes
                 .unwrap_or(env.emit_gen_ai_attributes),
+            emit_openinference_attributes: self
+                .emit_openinference_attributes
+                .unwrap_or(env.emit_openinference_attributes),
+            embedding_vector_encoding: self
+                .embedding_vector_encoding
+                .unwrap_or(env.embedding_vector_encoding),
+            skip_empty_values: self.skip_empty_values.unwrap_or(env.skip_empty_values),
+            content_as_events: self.content_as_events.unwrap_or(env.content_as_events),
+            flatten_messages_to_input: self
+                .flatten_messages_to_input
+                .unwrap_or(env.flatten_messages_to_input),
+            default_tool_calls_finish_reason: self
+                .default_tool_calls_finish_reason
+                .unwrap_or(env.default_tool_calls_finish_reason),
+            key_overrides: self.key_overrides,
+            include_kind_in_name: self
+                .include_kind_in_name
+                .unwrap_or(env.include_kind_in_name),
+            lowercase_span_kind: self.lowercase_span_kind.unwrap_or(env.lowercase_span_kind),
+            component_name: self.component_name.or(env.component_name),
+            max_documents_recorded: self.max_documents_recorded.or(env.max_documents_recorded),
+            attribute_prefix: self.attribute_prefix.or(env.attribute_prefix),
+            span_level: self.span_level.unwrap_or(env.span_level),
+            llm_defaults: self.llm_defaults.unwrap_or(env.llm_defaults),
         }
     }
 }
@@ -297,21 +542,21 @@ impl TraceConfigBuilder {
 // Env parsing helpers
 // =============================================================================
 
-fn parse_bool_env(key: &str, default: bool) -> bool {
-    match env::var(key) {
-        Ok(val) => match val.to_lowercase().as_str() {
+fn parse_bool(vars: &HashMap<String, String>, key: &str, default: bool) -> bool {
+    match vars.get(key) {
+        Some(val) => match val.to_lowercase().as_str() {
             "true" | "1" => true,
             "false" | "0" => false,
             _ => default,
         },
-        Err(_) => default,
+        None => default,
     }
 }
 
-fn parse_usize_env(key: &str, default: usize) -> usize {
-    match env::var(key) {
-        Ok(val) => val.parse().unwrap_or(default),
-        Err(_) => default,
+fn parse_usize(vars: &HashMap<String, String>, key: &str, default: usize) -> usize {
+    match vars.get(key) {
+        Some(val) => val.parse().unwrap_or(default),
+        None => default,
     }
 }
 
@@ -339,8 +584,28 @@ mod tests {
         assert!(!config.hide_embeddings_text);
         assert!(!config.hide_prompts);
         assert!(!config.hide_choices);
+        assert!(!config.hide_agent_state);
+        assert!(!config.hide_tool_parameters);
+        assert!(!config.hide_output_schema);
         assert_eq!(config.base64_image_max_length, 32_000);
         assert!(config.emit_gen_ai_attributes);
+        assert!(config.emit_openinference_attributes);
+        assert_eq!(
+            config.embedding_vector_encoding,
+            EmbeddingVectorEncoding::Float
+        );
+        assert!(config.skip_empty_values);
+        assert!(!config.content_as_events);
+        assert!(!config.flatten_messages_to_input);
+        assert!(!config.default_tool_calls_finish_reason);
+        assert!(config.key_overrides.is_empty());
+        assert!(!config.include_kind_in_name);
+        assert!(!config.lowercase_span_kind);
+        assert_eq!(config.component_name, None);
+        assert_eq!(config.max_documents_recorded, None);
+        assert_eq!(config.attribute_prefix, None);
+        assert_eq!(config.span_level, tracing::Level::INFO);
+        assert_eq!(config.llm_defaults, LlmDefaults::default());
     }
 
     #[test]
@@ -388,6 +653,37 @@ mod tests {
         env::remove_var(ENV_BASE64_IMAGE_MAX_LENGTH);
     }
 
+    #[test]
+    fn test_from_map_reads_values() {
+        let vars = HashMap::from([
+            (ENV_HIDE_INPUTS.to_string(), "true".to_string()),
+            (ENV_HIDE_AGENT_STATE.to_string(), "1".to_string()),
+            (ENV_BASE64_IMAGE_MAX_LENGTH.to_string(), "8000".to_string()),
+        ]);
+
+        let config = TraceConfig::from_map(&vars);
+
+        assert!(config.hide_inputs);
+        assert!(config.hide_agent_state);
+        assert!(!config.hide_outputs);
+        assert_eq!(config.base64_image_max_length, 8_000);
+    }
+
+    #[test]
+    fn test_from_map_empty_and_invalid_use_defaults() {
+        let config = TraceConfig::from_map(&HashMap::new());
+        assert!(!config.hide_inputs);
+        assert_eq!(config.base64_image_max_length, 32_000);
+
+        let vars = HashMap::from([
+            (ENV_HIDE_OUTPUTS.to_string(), "yes".to_string()),
+            (ENV_BASE64_IMAGE_MAX_LENGTH.to_string(), "-1".to_string()),
+        ]);
+        let config = TraceConfig::from_map(&vars);
+        assert!(!config.hide_outputs);
+        assert_eq!(config.base64_image_max_length, 32_000);
+    }
+
     #[test]
     fn test_builder_overrides_env() {
         let _lock = ENV_LOCK.lock().unwrap();
@@ -459,6 +755,28 @@ mod tests {
         assert!(!config.should_hide_choices());
     }
 
+    #[test]
+    fn test_compound_hide_outputs_implies_agent_state() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let config = TraceConfig::builder().hide_outputs(true).build();
+        assert!(config.should_hide_agent_state());
+
+        let config2 = TraceConfig::builder().hide_agent_state(true).build();
+        assert!(config2.should_hide_agent_state());
+        assert!(!config2.should_hide_output_messages());
+    }
+
+    #[test]
+    fn test_compound_hide_tool_parameters() {
+        let _lock = ENV_LOCK.lock().unwrap();
+        let config = TraceConfig::builder().hide_inputs(true).build();
+        assert!(config.should_hide_tool_parameters());
+
+        let config2 = TraceConfig::builder().hide_tool_parameters(true).build();
+        assert!(config2.should_hide_tool_parameters());
+        assert!(!config2.should_hide_input_text());
+    }
+
     #[test]
     fn test_deprecated_hide_embedding_vectors() {
         let _lock = ENV_LOCK.lock().unwrap();
@@ -22,19 +22,51 @@
 //! // ... perform LLM call ...
 //! ```
 
+mod macros;
+
+#[cfg(feature = "testing")]
+pub mod analytics;
 pub mod config;
+pub mod messages;
+#[cfg(feature = "testing")]
+pub mod mock;
+pub mod provider;
 pub mod span_builder;
+pub mod tokenizer;
 
-pub use config::{TraceConfig, TraceConfigBuilder, REDACTED};
+#[cfg(feature = "testing")]
+pub use analytics::{flatten_attributes, sum_cost};
+pub use config::{EmbeddingVectorEncoding, LlmDefaults, TraceConfig, TraceConfigBuilder, REDACTED};
+pub use messages::{flatten_messages, unflatten_messages, Message, MessagePart, ToolCall};
+#[cfg(feature = "testing")]
+pub use mock::MockSpan;
+pub use provider::{infer_provider_from_model, ProviderInfo};
+#[cfg(feature = "serde")]
+pub use span_builder::record_tool_call_json;
+pub use span_builder::{
+    decorate_agent, decorate_chain, decorate_embedding, decorate_llm, decorate_retriever,
+    decorate_tool, openinference_span_from_gen_ai,
+};
 pub use span_builder::{
-    record_error, record_output_message, record_output_tool_call, record_output_value,
-    record_reranker_output_documents, record_retrieval_documents, record_token_usage,
+    record_cache_cost, record_chat_completion, record_choices, record_citations,
+    record_content_filter, record_effective_params, record_embedding_token_usage,
+    record_embedding_vector, record_embeddings_batch, record_error, record_error_kind,
+    record_error_with_config, record_estimated_usage, record_message_tokens, record_moderation,
+    record_output_message, record_output_message_count, record_output_tool_call,
+    record_output_tool_call_with_config, record_output_tool_calls, record_output_valid,
+    record_output_value, record_provider_metadata, record_queue_time, record_rate_limit,
+    record_reasoning_ratio, record_rendered_prompt, record_reranker_output_documents,
+    record_response_object, record_retrieval_documents, record_safety_ratings, record_throughput,
+    record_timestamps, record_timing_split, record_token_details, record_token_usage,
+    record_token_usage_with_config, record_tool_duration,
 };
 pub use span_builder::{
-    AgentSpanBuilder, ChainSpanBuilder, Document, EmbeddingSpanBuilder, EvaluatorSpanBuilder,
-    GuardrailSpanBuilder, LlmSpanBuilder, RerankerSpanBuilder, RetrieverSpanBuilder,
-    ToolSpanBuilder,
+    AgentSpanBuilder, AttributeSink, CacheCost, ChainSpanBuilder, Document, EmbeddingSpanBuilder,
+    EvaluatorSpanBuilder, FinishReason, GenAiSpanBuilder, GenericSpanBuilder, GuardrailSpanBuilder,
+    LlmErrorKind, LlmSpanBuilder, LlmSpanBuilderView, RerankerSpanBuilder, RetrieverSpanBuilder,
+    TokenDetails, ToolSpanBuilder, UsageAccumulator,
 };
+pub use tokenizer::Tokenizer;
 
 /// Re-export semantic conventions for convenience.
 pub use openinference_semantic_conventions as semconv;
@@ -0,0 +1,25 @@
+//! Declarative shorthands for the span builders.
+
+/// Build an LLM span with attributes given inline.
+///
+/// `model` is required and must come first. Every other `name: value` pair
+/// calls the single-argument [`LlmSpanBuilder`](crate::LlmSpanBuilder) setter
+/// of the same name, in order, before `build()`.
+///
+/// ```rust,ignore
+/// use openinference_instrumentation::llm_span;
+///
+/// let span = llm_span! {
+///     model: "gpt-4",
+///     provider: "openai",
+///     temperature: 0.7,
+/// };
+/// ```
+#[macro_export]
+macro_rules! llm_span {
+    (model: $model:expr $(, $setter:ident : $value:expr)* $(,)?) => {
+        $crate::span_builder::LlmSpanBuilder::new($model)
+            $(.$setter($value))*
+            .build()
+    };
+}
@@ -0,0 +1,447 @@
+//! Conversion between structured and indexed message representations.
+//!
+//! The OTel GenAI conventions record messages as a single JSON attribute
+//! (`gen_ai.input.messages`), while OpenInference flattens them into indexed
+//! attributes (`llm.input_messages.{i}.message.*`). These helpers convert
+//! between the two so instrumentations can emit or consume either form.
+//!
+//! The structured form is an array of messages, each with a `role` and either
+//! a plain `content` string or a list of `parts`:
+//!
+//! ```json
+//! [
+//!   {"role": "system", "content": "Be brief."},
+//!   {"role": "user", "parts": [{"type": "text", "content": "Hello"}]}
+//! ]
+//! ```
+
+use std::collections::BTreeMap;
+
+use openinference_semantic_conventions::attributes;
+use opentelemetry::{KeyValue, Value};
+use serde_json::{json, Map};
+
+const INPUT_MESSAGES_PREFIX: &str = "llm.input_messages.";
+
+/// Flatten a structured JSON message list into indexed OpenInference attributes.
+///
+/// A `content` string becomes `llm.input_messages.{i}.message.content`; each
+/// entry in `parts` becomes a `message.contents.{j}.message_content.*` pair.
+/// Returns an empty list if `json` is not a JSON array.
+pub fn flatten_messages(json: &str) -> Vec<KeyValue> {
+    let Ok(serde_json::Value::Array(messages)) = serde_json::from_str(json) else {
+        return Vec::new();
+    };
+
+    let mut attrs = Vec::new();
+    for (i, message) in messages.iter().enumerate() {
+        if let Some(role) = message.get("role").and_then(|r| r.as_str()) {
+            attrs.push(KeyValue::new(
+                attributes::llm::input_messages::role(i),
+                role.to_string(),
+            ));
+        }
+        if let Some(content) = message.get("content").and_then(|c| c.as_str()) {
+            attrs.push(KeyValue::new(
+                attributes::llm::input_messages::content(i),
+                content.to_string(),
+            ));
+        }
+        let parts = message.get("parts").and_then(|p| p.as_array());
+        for (j, part) in parts.into_iter().flatten().enumerate() {
+            if let Some(kind) = part.get("type").and_then(|t| t.as_str()) {
+                attrs.push(KeyValue::new(
+                    attributes::llm::input_messages::content_type(i, j),
+                    kind.to_string(),
+                ));
+            }
+            if let Some(text) = part.get("content").and_then(|c| c.as_str()) {
+                attrs.push(KeyValue::new(
+                    attributes::llm::input_messages::content_text(i, j),
+                    text.to_string(),
+                ));
+            }
+        }
+    }
+    attrs
+}
+
+/// Rebuild a structured JSON message list from indexed OpenInference attributes.
+///
+/// This is the inverse of [`flatten_messages`]. Attributes that are not
+/// `llm.input_messages.*` string values are ignored.
+pub fn unflatten_messages(attrs: &[KeyValue]) -> serde_json::Value {
+    #[derive(Default)]
+    struct Message {
+        role: Option<String>,
+        content: Option<String>,
+        parts: BTreeMap<usize, Map<String, serde_json::Value>>,
+    }
+
+    let mut messages: BTreeMap<usize, Message> = BTreeMap::new();
+    for kv in attrs {
+        let Value::String(value) = &kv.value else {
+            continue;
+        };
+        let Some(rest) = kv.key.as_str().strip_prefix(INPUT_MESSAGES_PREFIX) else {
+            continue;
+        };
+        let Some((index, field)) = rest.split_once('.') else {
+            continue;
+        };
+        let Ok(index) = index.parse::<usize>() else {
+            continue;
+        };
+        let message = messages.entry(index).or_default();
+        let value = value.as_str().to_string();
+
+        match field {
+            "message.role" => message.role = Some(value),
+            "message.content" => message.content = Some(value),
+            _ => {
+                let Some((part_index, part_field)) = field
+                    .strip_prefix("message.contents.")
+                    .and_then(|f| f.split_once('.'))
+                else {
+                    continue;
+                };
+                let Ok(part_index) = part_index.parse::<usize>() else {
+                    continue;
+                };
+                let part = message.parts.entry(part_index).or_default();
+                match part_field {
+                    "message_content.type" => {
+                        part.insert("type".into(), value.into());
+                    }
+                    "message_content.text" => {
+                        part.insert("content".into(), value.into());
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    let messages = messages
+        .into_values()
+        .map(|message| {
+            let mut obj = Map::new();
+            if let Some(role) = message.role {
+                obj.insert("role".into(), role.into());
+            }
+            if let Some(content) = message.content {
+                obj.insert("content".into(), content.into());
+            }
+            if !message.parts.is_empty() {
+                let parts = message.parts.into_values().map(Into::into).collect();
+                obj.insert("parts".into(), serde_json::Value::Array(parts));
+            }
+            serde_json::Value::Object(obj)
+        })
+        .collect::<Vec<_>>();
+    json!(messages)
+}
+
+/// A chat message in a provider-neutral shape.
+///
+/// With the `serde` feature, [`Message::from_openai_json`] and
+/// [`Message::from_anthropic_json`] parse provider message objects into this
+/// type.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Message {
+    pub role: String,
+    /// Plain string content, when the provider sent a single string.
+    pub content: Option<String>,
+    /// Structured content parts, when the provider sent a content array.
+    pub parts: Vec<MessagePart>,
+    pub tool_calls: Vec<ToolCall>,
+    /// The tool call this message answers (tool result messages only).
+    pub tool_call_id: Option<String>,
+}
+
+/// A single part of a multi-part message.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MessagePart {
+    Text(String),
+    /// An image, as a URL or `data:` URI.
+    Image(String),
+}
+
+/// A tool call requested by the model.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ToolCall {
+    pub id: Option<String>,
+    pub name: String,
+    /// Arguments as a JSON string.
+    pub arguments: String,
+}
+
+#[cfg(feature = "serde")]
+impl Message {
+    /// Parse an OpenAI chat completion message.
+    ///
+    /// Handles string or array `content` (`text` and `image_url` parts),
+    /// `tool_calls`, and `tool_call_id`. Returns `None` if `role` is missing.
+    pub fn from_openai_json(value: &serde_json::Value) -> Option<Message> {
+        let mut message = Message {
+            role: value.get("role")?.as_str()?.to_string(),
+            tool_call_id: str_field(value, "tool_call_id"),
+            ..Default::default()
+        };
+
+        match value.get("content") {
+            Some(serde_json::Value::String(text)) => message.content = Some(text.clone()),
+            Some(serde_json::Value::Array(parts)) => {
+                message.parts = parts
+                    .iter()
+                    .filter_map(|part| match part.get("type")?.as_str()? {
+                        "text" => Some(MessagePart::Text(str_field(part, "text")?)),
+                        "image_url" => Some(MessagePart::Image(str_field(
+                            part.get("image_url")?,
+                            "url",
+                        )?)),
+                        _ => None,
+                    })
+                    .collect();
+            }
+            _ => {}
+        }
+
+        let tool_calls = value.get("tool_calls").and_then(|t| t.as_array());
+        message.tool_calls = tool_calls
+            .into_iter()
+            .flatten()
+            .filter_map(|call| {
+                let function = call.get("function")?;
+                Some(ToolCall {
+                    id: str_field(call, "id"),
+                    name: str_field(function, "name")?,
+                    arguments: str_field(function, "arguments").unwrap_or_default(),
+                })
+            })
+            .collect();
+
+        Some(message)
+    }
+
+    /// Parse an Anthropic Messages API message.
+    ///
+    /// Handles string or array `content` with `text`, `image`, `tool_use`,
+    /// and `tool_result` blocks. `tool_use` input is serialized to a JSON
+    /// string; a `tool_result` sets [`tool_call_id`](Message::tool_call_id)
+    /// and contributes its text. Returns `None` if `role` is missing.
+    pub fn from_anthropic_json(value: &serde_json::Value) -> Option<Message> {
+        let mut message = Message {
+            role: value.get("role")?.as_str()?.to_string(),
+            ..Default::default()
+        };
+
+        let blocks = match value.get("content") {
+            Some(serde_json::Value::String(text)) => {
+                message.content = Some(text.clone());
+                return Some(message);
+            }
+            Some(serde_json::Value::Array(blocks)) => blocks,
+            _ => return Some(message),
+        };
+
+        for block in blocks {
+            match block.get("type").and_then(|t| t.as_str()) {
+                Some("text") => {
+                    if let Some(text) = str_field(block, "text") {
+                        message.parts.push(MessagePart::Text(text));
+                    }
+                }
+                Some("image") => {
+                    if let Some(image) = block.get("source").and_then(anthropic_image) {
+                        message.parts.push(MessagePart::Image(image));
+                    }
+                }
+                Some("tool_use") => {
+                    let Some(name) = str_field(block, "name") else {
+                        continue;
+                    };
+                    message.tool_calls.push(ToolCall {
+                        id: str_field(block, "id"),
+                        name,
+                        arguments: block
+                            .get("input")
+                            .map(|input| input.to_string())
+                            .unwrap_or_else(|| "{}".to_string()),
+                    });
+                }
+                Some("tool_result") => {
+                    message.tool_call_id = str_field(block, "tool_use_id");
+                    match block.get("content") {
+                        Some(serde_json::Value::String(text)) => {
+                            message.parts.push(MessagePart::Text(text.clone()));
+                        }
+                        Some(serde_json::Value::Array(inner)) => {
+                            message.parts.extend(
+                                inner
+                                    .iter()
+                                    .filter_map(|b| str_field(b, "text"))
+                                    .map(MessagePart::Text),
+                            );
+                        }
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Some(message)
+    }
+}
+
+#[cfg(feature = "serde")]
+fn str_field(value: &serde_json::Value, key: &str) -> Option<String> {
+    value.get(key)?.as_str().map(str::to_string)
+}
+
+/// Converts an Anthropic image `source` into a URL or `data:` URI.
+#[cfg(feature = "serde")]
+fn anthropic_image(source: &serde_json::Value) -> Option<String> {
+    match source.get("type")?.as_str()? {
+        "url" => str_field(source, "url"),
+        "base64" => Some(format!(
+            "data:{};base64,{}",
+            source.get("media_type")?.as_str()?,
+            source.get("data")?.as_str()?
+        )),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip_two_messages() {
+        let input = r#"[
+            {"role": "system", "content": "Be brief."},
+            {"role": "user", "parts": [{"type": "text", "content": "Hello"}]}
+        ]"#;
+
+        let attrs = flatten_messages(input);
+        let find = |key: &str| {
+            attrs
+                .iter()
+                .find(|kv| kv.key.as_str() == key)
+                .map(|kv| kv.value.as_str().into_owned())
+        };
+        assert_eq!(
+            find("llm.input_messages.0.message.content").as_deref(),
+            Some("Be brief.")
+        );
+        assert_eq!(
+            find("llm.input_messages.1.message.contents.0.message_content.text").as_deref(),
+            Some("Hello")
+        );
+
+        let expected: serde_json::Value = serde_json::from_str(input).unwrap();
+        assert_eq!(unflatten_messages(&attrs), expected);
+    }
+
+    #[test]
+    fn test_flatten_invalid_json_is_empty() {
+        assert!(flatten_messages("not json").is_empty());
+        assert!(flatten_messages(r#"{"role": "user"}"#).is_empty());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_message_from_openai_json() {
+        let value = json!({
+            "role": "assistant",
+            "content": [
+                {"type": "text", "text": "Checking the weather."},
+                {"type": "image_url", "image_url": {"url": "https://example.com/map.png"}}
+            ],
+            "tool_calls": [{
+                "id": "call_1",
+                "type": "function",
+                "function": {"name": "get_weather", "arguments": "{\"city\":\"Paris\"}"}
+            }]
+        });
+
+        let message = Message::from_openai_json(&value).unwrap();
+        assert_eq!(message.role, "assistant");
+        assert_eq!(message.content, None);
+        assert_eq!(
+            message.parts,
+            vec![
+                MessagePart::Text("Checking the weather.".into()),
+                MessagePart::Image("https://example.com/map.png".into()),
+            ]
+        );
+        assert_eq!(
+            message.tool_calls,
+            vec![ToolCall {
+                id: Some("call_1".into()),
+                name: "get_weather".into(),
+                arguments: r#"{"city":"Paris"}"#.into(),
+            }]
+        );
+
+        let tool = json!({"role": "tool", "tool_call_id": "call_1", "content": "18C"});
+        let tool = Message::from_openai_json(&tool).unwrap();
+        assert_eq!(tool.content.as_deref(), Some("18C"));
+        assert_eq!(tool.tool_call_id.as_deref(), Some("call_1"));
+
+        assert_eq!(Message::from_openai_json(&json!({"content": "hi"})), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_message_from_anthropic_json() {
+        let value = json!({
+            "role": "assistant",
+            "content": [
+                {"type": "text", "text": "Checking the weather."},
+                {"type": "tool_use", "id": "toolu_1", "name": "get_weather", "input": {"city": "Paris"}}
+            ]
+        });
+
+        let message = Message::from_anthropic_json(&value).unwrap();
+        assert_eq!(message.role, "assistant");
+        assert_eq!(
+            message.parts,
+            vec![MessagePart::Text("Checking the weather.".into())]
+        );
+        assert_eq!(
+            message.tool_calls,
+            vec![ToolCall {
+                id: Some("toolu_1".into()),
+                name: "get_weather".into(),
+                arguments: r#"{"city":"Paris"}"#.into(),
+            }]
+        );
+
+        let result = json!({
+            "role": "user",
+            "content": [
+                {"type": "tool_result", "tool_use_id": "toolu_1", "content": "18C"},
+                {"type": "image", "source": {"type": "base64", "media_type": "image/png", "data": "AAAA"}}
+            ]
+        });
+        let result = Message::from_anthropic_json(&result).unwrap();
+        assert_eq!(result.tool_call_id.as_deref(), Some("toolu_1"));
+        assert_eq!(
+            result.parts,
+            vec![
+                MessagePart::Text("18C".into()),
+                MessagePart::Image("data:image/png;base64,AAAA".into()),
+            ]
+        );
+
+        let plain =
+            Message::from_anthropic_json(&json!({"role": "user", "content": "Hi"})).unwrap();
+        assert_eq!(plain.content.as_deref(), Some("Hi"));
+    }
+}
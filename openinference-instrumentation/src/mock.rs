@@ -0,0 +1,157 @@
+//! An in-memory recording target for fast builder tests.
+//!
+//! [`MockSpan`] implements [`AttributeSink`], so builders can record into it
+//! via `record_into` and tests can assert attributes without standing up an
+//! OTel pipeline. Available with the `testing` feature.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use opentelemetry::{Key, KeyValue, Value};
+
+use crate::span_builder::AttributeSink;
+
+/// Collects attributes into a `HashMap` and events into a list.
+///
+/// Unlike an exported span, a key recorded twice keeps only its last value.
+#[derive(Debug, Default)]
+pub struct MockSpan {
+    attributes: RefCell<HashMap<String, Value>>,
+    events: RefCell<Vec<(String, Vec<KeyValue>)>>,
+}
+
+impl MockSpan {
+    /// Create an empty mock span.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The value recorded for `key`, if any.
+    pub fn get(&self, key: &str) -> Option<Value> {
+        self.attributes.borrow().get(key).cloned()
+    }
+
+    /// All recorded attributes.
+    pub fn attributes(&self) -> HashMap<String, Value> {
+        self.attributes.borrow().clone()
+    }
+
+    /// All recorded events as `(name, attributes)` pairs, in order.
+    pub fn events(&self) -> Vec<(String, Vec<KeyValue>)> {
+        self.events.borrow().clone()
+    }
+}
+
+impl AttributeSink for MockSpan {
+    fn record_attribute(&self, key: Key, value: Value) {
+        self.attributes
+            .borrow_mut()
+            .insert(key.as_str().to_string(), value);
+    }
+
+    fn record_event(&self, name: &'static str, attributes: Vec<KeyValue>) {
+        self.events
+            .borrow_mut()
+            .push((name.to_string(), attributes));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::span_builder::{
+        AgentSpanBuilder, ChainSpanBuilder, EmbeddingSpanBuilder, EvaluatorSpanBuilder,
+        GenericSpanBuilder, GuardrailSpanBuilder, LlmSpanBuilder, RerankerSpanBuilder,
+        RetrieverSpanBuilder, ToolSpanBuilder,
+    };
+    use openinference_semantic_conventions::SpanKind;
+
+    #[test]
+    fn test_mock_span_records_llm_attributes() {
+        let mock = MockSpan::new();
+        LlmSpanBuilder::new("gpt-4")
+            .provider("openai")
+            .temperature(0.5)
+            .input_message("user", "Hello")
+            .record_into(&mock);
+
+        assert_eq!(mock.get("openinference.span.kind"), Some("LLM".into()));
+        assert_eq!(mock.get("llm.model_name"), Some("gpt-4".into()));
+        assert_eq!(mock.get("llm.provider"), Some("openai".into()));
+        assert_eq!(mock.get("gen_ai.request.temperature"), Some(0.5.into()));
+        assert_eq!(
+            mock.get("llm.input_messages.0.message.content"),
+            Some("Hello".into())
+        );
+        assert!(mock.events().is_empty());
+    }
+
+    #[test]
+    fn test_mock_span_records_every_builder_kind() {
+        let record = |f: &dyn Fn(&MockSpan)| {
+            let mock = MockSpan::new();
+            f(&mock);
+            mock.get("openinference.span.kind")
+        };
+
+        assert_eq!(
+            record(&|m| EmbeddingSpanBuilder::new("ada").record_into(m)),
+            Some("EMBEDDING".into())
+        );
+        assert_eq!(
+            record(&|m| ChainSpanBuilder::new("router").record_into(m)),
+            Some("CHAIN".into())
+        );
+        assert_eq!(
+            record(&|m| ToolSpanBuilder::new("search").record_into(m)),
+            Some("TOOL".into())
+        );
+        assert_eq!(
+            record(&|m| RetrieverSpanBuilder::new("vector-db").record_into(m)),
+            Some("RETRIEVER".into())
+        );
+        assert_eq!(
+            record(&|m| AgentSpanBuilder::new("planner").record_into(m)),
+            Some("AGENT".into())
+        );
+        assert_eq!(
+            record(&|m| RerankerSpanBuilder::new("rerank-v3").record_into(m)),
+            Some("RERANKER".into())
+        );
+        assert_eq!(
+            record(&|m| GuardrailSpanBuilder::new("pii").record_into(m)),
+            Some("GUARDRAIL".into())
+        );
+        assert_eq!(
+            record(&|m| EvaluatorSpanBuilder::new("relevance").record_into(m)),
+            Some("EVALUATOR".into())
+        );
+        assert_eq!(
+            record(&|m| GenericSpanBuilder::new(SpanKind::Chain, "step").record_into(m)),
+            Some("CHAIN".into())
+        );
+    }
+
+    #[test]
+    fn test_mock_span_records_builder_attributes() {
+        let mock = MockSpan::new();
+        AgentSpanBuilder::new("planner")
+            .description("Plans trips")
+            .emit_gen_ai(false)
+            .record_into(&mock);
+        assert_eq!(mock.get("agent.name"), Some("planner".into()));
+        assert_eq!(mock.get("gen_ai.agent.name"), None);
+
+        let mock = MockSpan::new();
+        EmbeddingSpanBuilder::new("ada")
+            .text("hello")
+            .dimensions(3)
+            .record_into(&mock);
+        assert_eq!(mock.get("embedding.model_name"), Some("ada".into()));
+        assert_eq!(
+            mock.get("embedding.embeddings.0.embedding.text"),
+            Some("hello".into())
+        );
+        assert_eq!(mock.get("embedding.dimensions"), Some(3i64.into()));
+    }
+}
@@ -0,0 +1,136 @@
+//! Inference of the LLM provider and system from a model name.
+//!
+//! Instrumentations often only know the model string passed to the client.
+//! [`infer_provider_from_model`] maps well-known model names, Bedrock model
+//! ids, and Bedrock ARNs to `llm.provider` / `llm.system` values.
+
+/// Provider and underlying system inferred from a model name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProviderInfo {
+    /// The hosting provider (e.g., "openai", "aws.bedrock").
+    pub provider: &'static str,
+    /// The model family's system (e.g., "openai", "anthropic").
+    pub system: &'static str,
+}
+
+/// Model-name prefixes for models served directly by their vendor.
+const MODEL_PREFIXES: &[(&str, ProviderInfo)] = &[
+    ("gpt-", direct("openai")),
+    ("o1", direct("openai")),
+    ("o3", direct("openai")),
+    ("o4", direct("openai")),
+    ("text-embedding-", direct("openai")),
+    ("claude", direct("anthropic")),
+    ("gemini", direct("google")),
+    ("mistral", direct("mistralai")),
+    ("mixtral", direct("mistralai")),
+    ("codestral", direct("mistralai")),
+    ("command", direct("cohere")),
+];
+
+/// Vendor prefixes of Bedrock model ids (`{vendor}.{model}`).
+const BEDROCK_VENDORS: &[(&str, &str)] = &[
+    ("anthropic", "anthropic"),
+    ("amazon", "amazon"),
+    ("meta", "meta"),
+    ("cohere", "cohere"),
+    ("mistral", "mistralai"),
+    ("ai21", "ai21"),
+    ("deepseek", "deepseek"),
+];
+
+/// Bedrock cross-region inference profile prefixes (e.g., `us.anthropic...`).
+const BEDROCK_REGION_PREFIXES: &[&str] = &["us", "eu", "apac", "us-gov"];
+
+const BEDROCK_PROVIDER: &str = "aws.bedrock";
+const BEDROCK_ARN_PREFIX: &str = "arn:aws:bedrock:";
+
+const fn direct(system: &'static str) -> ProviderInfo {
+    ProviderInfo {
+        provider: system,
+        system,
+    }
+}
+
+/// Infer the provider and system from a model name.
+///
+/// Recognizes Bedrock ARNs (`arn:aws:bedrock:...`) and Bedrock model ids
+/// (`anthropic.claude-3-sonnet-...`), which map to the `aws.bedrock` provider
+/// with the vendor as the system. Returns `None` for unknown models.
+pub fn infer_provider_from_model(model: &str) -> Option<ProviderInfo> {
+    let model = model.trim();
+
+    if let Some(resource) = model.strip_prefix(BEDROCK_ARN_PREFIX) {
+        // arn:aws:bedrock:{region}:{account}:{resource-type}/{model-id}
+        let model_id = resource.rsplit('/').next().unwrap_or(resource);
+        let system = bedrock_system(model_id).unwrap_or("unknown");
+        return Some(ProviderInfo {
+            provider: BEDROCK_PROVIDER,
+            system,
+        });
+    }
+
+    if let Some(system) = bedrock_system(model) {
+        return Some(ProviderInfo {
+            provider: BEDROCK_PROVIDER,
+            system,
+        });
+    }
+
+    let lower = model.to_ascii_lowercase();
+    MODEL_PREFIXES
+        .iter()
+        .find(|(prefix, _)| lower.starts_with(prefix))
+        .map(|(_, info)| *info)
+}
+
+/// Returns the system for a Bedrock model id like `anthropic.claude-v2`,
+/// optionally prefixed with a cross-region inference profile.
+fn bedrock_system(model_id: &str) -> Option<&'static str> {
+    let (head, rest) = model_id.split_once('.')?;
+    let (vendor, _) = if BEDROCK_REGION_PREFIXES.contains(&head) {
+        rest.split_once('.')?
+    } else {
+        (head, rest)
+    };
+    BEDROCK_VENDORS
+        .iter()
+        .find(|(prefix, _)| *prefix == vendor)
+        .map(|(_, system)| *system)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_infer_bedrock_arn() {
+        let info = infer_provider_from_model(
+            "arn:aws:bedrock:us-east-1::foundation-model/anthropic.claude-3-sonnet-20240229-v1:0",
+        )
+        .unwrap();
+        assert_eq!(info.provider, "aws.bedrock");
+        assert_eq!(info.system, "anthropic");
+    }
+
+    #[test]
+    fn test_infer_bedrock_model_id() {
+        let info = infer_provider_from_model("anthropic.claude-3-sonnet-20240229-v1:0").unwrap();
+        assert_eq!(info.provider, "aws.bedrock");
+        assert_eq!(info.system, "anthropic");
+
+        let profile = infer_provider_from_model("us.meta.llama3-2-90b-instruct-v1:0").unwrap();
+        assert_eq!(profile.provider, "aws.bedrock");
+        assert_eq!(profile.system, "meta");
+    }
+
+    #[test]
+    fn test_infer_direct_models() {
+        assert_eq!(infer_provider_from_model("gpt-4o"), Some(direct("openai")));
+        assert_eq!(
+            infer_provider_from_model("claude-3-5-sonnet-latest"),
+            Some(direct("anthropic"))
+        );
+        assert_eq!(infer_provider_from_model("my-local-model"), None);
+    }
+}
@@ -5,12 +5,142 @@
 //! All attributes are set via `OpenTelemetrySpanExt::set_attribute()` so that
 //! dynamic, indexed keys (e.g. `llm.input_messages.0.message.role`) work correctly.
 
-use crate::config::{TraceConfig, REDACTED};
+use crate::config::{EmbeddingVectorEncoding, TraceConfig, REDACTED};
+use crate::tokenizer::Tokenizer;
 use openinference_semantic_conventions::attributes;
 use openinference_semantic_conventions::gen_ai;
 use openinference_semantic_conventions::SpanKind;
+use opentelemetry::{Array, Key, KeyValue, StringValue, Value};
+use std::cell::Cell;
+use std::time::Duration;
 use tracing::Span;
 use tracing_opentelemetry::OpenTelemetrySpanExt;
+#[cfg(feature = "registry")]
+use tracing_subscriber::registry::{LookupSpan, Registry};
+
+/// Create a span at a runtime `tracing::Level`.
+///
+/// The `span!` macros need a constant level for their static callsite, so
+/// this dispatches to one callsite per level.
+macro_rules! span_at_level {
+    ($level:expr, $name:expr, $($fields:tt)*) => {{
+        let level = $level;
+        if level == tracing::Level::TRACE {
+            tracing::trace_span!($name, $($fields)*)
+        } else if level == tracing::Level::DEBUG {
+            tracing::debug_span!($name, $($fields)*)
+        } else if level == tracing::Level::INFO {
+            tracing::info_span!($name, $($fields)*)
+        } else if level == tracing::Level::WARN {
+            tracing::warn_span!($name, $($fields)*)
+        } else {
+            tracing::error_span!($name, $($fields)*)
+        }
+    }};
+}
+
+// =============================================================================
+// Common Builder Attributes
+// =============================================================================
+
+/// Attributes every span builder accepts, set through
+/// [`common_attr_setters!`] and emitted by [`CommonAttrs::record`] along with
+/// the config's `component_name`.
+#[derive(Debug, Default)]
+struct CommonAttrs {
+    metadata: Option<String>,
+    metadata_fields: Vec<(String, Value)>,
+    replay: Option<bool>,
+    trace_root: Option<bool>,
+    trace_final: Option<bool>,
+}
+
+impl CommonAttrs {
+    fn record(&self, sink: &impl AttributeSink, config: &TraceConfig) {
+        if let Some(ref component) = config.component_name {
+            set_value(
+                sink,
+                config,
+                attributes::OPENINFERENCE_COMPONENT,
+                component.clone(),
+            );
+        }
+        if let Some(ref metadata) = self.metadata {
+            set_value(sink, config, attributes::METADATA, metadata.clone());
+        }
+        for (path, value) in &self.metadata_fields {
+            let key = Key::new(format!("{}.{path}", attributes::METADATA.as_str()));
+            set_value(sink, config, key, value.clone());
+        }
+        if let Some(replay) = self.replay {
+            set_value(sink, config, attributes::OPENINFERENCE_REPLAY, replay);
+        }
+        if let Some(root) = self.trace_root {
+            set_value(sink, config, attributes::OPENINFERENCE_TRACE_ROOT, root);
+        }
+        if let Some(is_final) = self.trace_final {
+            set_value(
+                sink,
+                config,
+                attributes::OPENINFERENCE_TRACE_FINAL,
+                is_final,
+            );
+        }
+    }
+}
+
+/// Setters for [`CommonAttrs`], expanded inside each builder's `impl` block.
+macro_rules! common_attr_setters {
+    () => {
+        /// Set arbitrary metadata as a JSON string.
+        ///
+        /// Debug builds assert that `json` parses; with the `serde` feature,
+        /// prefer `metadata_value` for values built in code.
+        pub fn metadata(mut self, json: impl Into<String>) -> Self {
+            let json = json.into();
+            debug_assert_valid_json(&json);
+            self.common.metadata = Some(json);
+            self
+        }
+
+        /// Set arbitrary metadata from a JSON value.
+        #[cfg(feature = "serde")]
+        pub fn metadata_value(mut self, value: serde_json::Value) -> Self {
+            self.common.metadata = Some(value.to_string());
+            self
+        }
+
+        /// Add a nested metadata field, recorded as `metadata.{path}`.
+        ///
+        /// `path` may be dotted (e.g., `"custom.foo"`). Fields accumulate and
+        /// are emitted at build time alongside [`metadata`](Self::metadata).
+        pub fn metadata_field(mut self, path: impl Into<String>, value: impl Into<Value>) -> Self {
+            self.common
+                .metadata_fields
+                .push((path.into(), value.into()));
+            self
+        }
+
+        /// Mark the span as a replay (e.g., an eval re-running historical
+        /// data), so cost dashboards can exclude it.
+        pub fn replay(mut self, replay: bool) -> Self {
+            self.common.replay = Some(replay);
+            self
+        }
+
+        /// Mark the span as the root of its trace, for trace-level rollups.
+        pub fn trace_root(mut self, root: bool) -> Self {
+            self.common.trace_root = Some(root);
+            self
+        }
+
+        /// Mark the span as producing the final output of its trace.
+        pub fn trace_final(mut self, is_final: bool) -> Self {
+            self.common.trace_final = Some(is_final);
+            self
+        }
+    };
+}
 
 // =============================================================================
 // LLM Span Builder
@@ -38,6 +168,8 @@ pub struct LlmSpanBuilder {
     model_name: String,
     provider: Option<String>,
     system: Option<String>,
+    model_version: Option<String>,
+    knowledge_cutoff: Option<String>,
     temperature: Option<f64>,
     top_p: Option<f64>,
     top_k: Option<i64>,
@@ -45,13 +177,58 @@ pub struct LlmSpanBuilder {
     frequency_penalty: Option<f64>,
     presence_penalty: Option<f64>,
     input_messages: Vec<(String, String)>, // (role, content)
+    conversation_turn_count: Option<i64>,
+    language: Option<String>,
+    system_prompt: Option<String>,
     invocation_parameters: Option<String>,
+    output_schema: Option<String>,
+    response_format: Option<String>,
+    streaming: Option<bool>,
+    cache_key: Option<String>,
+    cache_ttl_seconds: Option<i64>,
+    cache_breakpoints: Option<i64>,
+    semantic_cache_hit: Option<bool>,
+    semantic_cache_similarity: Option<f64>,
     input_value: Option<String>,
     output_value: Option<String>,
     tools: Vec<String>, // JSON schema strings
+    tool_count: Option<i64>,
+    tool_choice: Option<String>,
+    request_user: Option<String>,
+    server_address: Option<String>,
+    server_port: Option<i64>,
+    request_timestamp: Option<i64>,
+    response_timestamp: Option<i64>,
+    prompt_build_duration: Option<Duration>,
+    common: CommonAttrs,
+    emit_gen_ai: Option<bool>,
     config: TraceConfig,
 }
 
+/// A read-only view of an [`LlmSpanBuilder`]'s state, returned by
+/// [`LlmSpanBuilder::view`].
+///
+/// The setters own the plain field names, so state is read through this
+/// view rather than same-named getters. Fields reflect values set on the
+/// builder; `config.llm_defaults` only apply at build time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LlmSpanBuilderView<'a> {
+    /// The model name (empty for a deferred builder).
+    pub model_name: &'a str,
+    /// The provider, if set.
+    pub provider: Option<&'a str>,
+    /// The sampling temperature, if set.
+    pub temperature: Option<f64>,
+}
+
+impl Default for LlmSpanBuilder {
+    /// Equivalent to [`LlmSpanBuilder::new_deferred`]; the model must be set
+    /// with [`model_name`](LlmSpanBuilder::model_name) before building.
+    fn default() -> Self {
+        Self::new_deferred()
+    }
+}
+
 impl LlmSpanBuilder {
     /// Create a new LLM span builder with the given model name.
     pub fn new(model_name: impl Into<String>) -> Self {
@@ -59,6 +236,8 @@ impl LlmSpanBuilder {
             model_name: model_name.into(),
             provider: None,
             system: None,
+            model_version: None,
+            knowledge_cutoff: None,
             temperature: None,
             top_p: None,
             top_k: None,
@@ -66,20 +245,89 @@ impl LlmSpanBuilder {
             frequency_penalty: None,
             presence_penalty: None,
             input_messages: Vec::new(),
+            conversation_turn_count: None,
+            language: None,
+            system_prompt: None,
             invocation_parameters: None,
+            output_schema: None,
+            response_format: None,
+            streaming: None,
+            cache_key: None,
+            cache_ttl_seconds: None,
+            cache_breakpoints: None,
+            semantic_cache_hit: None,
+            semantic_cache_similarity: None,
             input_value: None,
             output_value: None,
             tools: Vec::new(),
+            tool_count: None,
+            tool_choice: None,
+            request_user: None,
+            server_address: None,
+            server_port: None,
+            request_timestamp: None,
+            response_timestamp: None,
+            prompt_build_duration: None,
+            common: CommonAttrs::default(),
+            emit_gen_ai: None,
             config: TraceConfig::default(),
         }
     }
 
+    /// Create an LLM span builder whose model is not yet known.
+    ///
+    /// Useful when the model is only chosen after routing (e.g., a
+    /// model-selector chain). Set it with [`model_name`](Self::model_name)
+    /// before calling [`build`](Self::build).
+    pub fn new_deferred() -> Self {
+        Self::new(String::new())
+    }
+
+    /// Set (or replace) the model name.
+    pub fn model_name(mut self, model_name: impl Into<String>) -> Self {
+        self.model_name = model_name.into();
+        self
+    }
+
     /// Set the configuration for this builder.
+    ///
+    /// At build time, fields from `config.llm_defaults` fill in any that
+    /// haven't been set; calling this again replaces those defaults.
     pub fn config(mut self, config: TraceConfig) -> Self {
         self.config = config;
         self
     }
 
+    /// Fill fields still unset from `config.llm_defaults`, through the
+    /// setters so defaults get the same debug checks as explicit values.
+    fn with_llm_defaults(mut self) -> Self {
+        let defaults = self.config.llm_defaults.clone();
+        if self.provider.is_none() {
+            self.provider = defaults.provider;
+        }
+        if self.system.is_none() {
+            self.system = defaults.system;
+        }
+        if let (None, Some(temp)) = (self.temperature, defaults.temperature) {
+            self = self.temperature(temp);
+        }
+        if let (None, Some(top_p)) = (self.top_p, defaults.top_p) {
+            self = self.top_p(top_p);
+        }
+        if let (None, Some(max_tokens)) = (self.max_tokens, defaults.max_tokens) {
+            self = self.max_tokens(max_tokens);
+        }
+        self
+    }
+
+    /// Override `emit_gen_ai_attributes` from the config for this span only.
+    pub fn emit_gen_ai(mut self, emit: bool) -> Self {
+        self.emit_gen_ai = Some(emit);
+        self
+    }
+
+    common_attr_setters!();
+
     /// Set the LLM provider (e.g., "openai", "anthropic", "mistral.rs").
     pub fn provider(mut self, provider: impl Into<String>) -> Self {
         self.provider = Some(provider.into());
@@ -92,37 +340,98 @@ impl LlmSpanBuilder {
         self
     }
 
+    /// Set the model version or snapshot (e.g., "2024-08-06").
+    pub fn model_version(mut self, version: impl Into<String>) -> Self {
+        self.model_version = Some(version.into());
+        self
+    }
+
+    /// Set the model's training-data knowledge cutoff (e.g., "2023-10").
+    pub fn knowledge_cutoff(mut self, cutoff: impl Into<String>) -> Self {
+        self.knowledge_cutoff = Some(cutoff.into());
+        self
+    }
+
+    /// Set the prompt-cache key, identifying the cached prompt prefix.
+    pub fn cache_key(mut self, key: impl Into<String>) -> Self {
+        self.cache_key = Some(key.into());
+        self
+    }
+
+    /// Set the prompt-cache entry's time-to-live in seconds.
+    pub fn cache_ttl_seconds(mut self, ttl: i64) -> Self {
+        self.cache_ttl_seconds = Some(ttl);
+        self
+    }
+
+    /// Set the number of prompt-cache breakpoints used.
+    pub fn cache_breakpoints(mut self, breakpoints: i64) -> Self {
+        self.cache_breakpoints = Some(breakpoints);
+        self
+    }
+
+    /// Mark whether the response was served from a semantic cache.
+    pub fn semantic_cache_hit(mut self, hit: bool) -> Self {
+        self.semantic_cache_hit = Some(hit);
+        self
+    }
+
+    /// Set the similarity score of the matched semantic cache entry.
+    pub fn semantic_cache_similarity(mut self, similarity: f64) -> Self {
+        self.semantic_cache_similarity = Some(similarity);
+        self
+    }
+
     /// Set the temperature parameter.
+    ///
+    /// Debug builds assert that it is non-negative.
     pub fn temperature(mut self, temp: f64) -> Self {
+        debug_assert!(temp >= 0.0, "temperature must be non-negative, got {temp}");
         self.temperature = Some(temp);
         self
     }
 
     /// Set the top_p (nucleus sampling) parameter.
+    ///
+    /// Debug builds assert that it is non-negative.
     pub fn top_p(mut self, top_p: f64) -> Self {
+        debug_assert!(top_p >= 0.0, "top_p must be non-negative, got {top_p}");
         self.top_p = Some(top_p);
         self
     }
 
     /// Set the top_k parameter.
+    ///
+    /// Debug builds assert that it is non-negative.
     pub fn top_k(mut self, top_k: i64) -> Self {
+        debug_assert!(top_k >= 0, "top_k must be non-negative, got {top_k}");
         self.top_k = Some(top_k);
         self
     }
 
     /// Set the maximum tokens to generate.
+    ///
+    /// Debug builds assert that it is non-negative.
     pub fn max_tokens(mut self, max_tokens: i64) -> Self {
+        debug_assert!(
+            max_tokens >= 0,
+            "max_tokens must be non-negative, got {max_tokens}"
+        );
         self.max_tokens = Some(max_tokens);
         self
     }
 
     /// Set the frequency penalty.
+    ///
+    /// Not validated: providers such as OpenAI accept negative penalties.
     pub fn frequency_penalty(mut self, penalty: f64) -> Self {
         self.frequency_penalty = Some(penalty);
         self
     }
 
     /// Set the presence penalty.
+    ///
+    /// Not validated: providers such as OpenAI accept negative penalties.
     pub fn presence_penalty(mut self, penalty: f64) -> Self {
         self.presence_penalty = Some(penalty);
         self
@@ -136,12 +445,65 @@ impl LlmSpanBuilder {
         self
     }
 
+    /// Set the detected language or locale of the input (e.g., "en").
+    pub fn language(mut self, language: impl Into<String>) -> Self {
+        self.language = Some(language.into());
+        self
+    }
+
+    /// Set how many prior conversation turns are in context.
+    ///
+    /// Recorded even when message content is hidden.
+    pub fn conversation_turn_count(mut self, turns: i64) -> Self {
+        self.conversation_turn_count = Some(turns);
+        self
+    }
+
+    /// Set a system prompt passed separately from the conversation.
+    ///
+    /// It is always recorded as input message 0 with `role=system`, shifting
+    /// messages added with [`input_message`](Self::input_message) up by one
+    /// regardless of call order. When `emit_gen_ai_attributes` is set it is
+    /// also recorded as `gen_ai.system_instructions`.
+    pub fn system_prompt(mut self, text: impl Into<String>) -> Self {
+        self.system_prompt = Some(text.into());
+        self
+    }
+
+    /// Set the JSON schema requested for structured output.
+    ///
+    /// Recorded as `llm.output_schema`, hidden only by `hide_output_schema`.
+    /// Debug builds assert that `schema` parses.
+    pub fn output_schema(mut self, schema: impl Into<String>) -> Self {
+        let schema = schema.into();
+        debug_assert_valid_json(&schema);
+        self.output_schema = Some(schema);
+        self
+    }
+
+    /// Set the requested response format as a JSON string
+    /// (e.g., `{"type": "json_object"}`), recorded as `llm.response_format`.
+    ///
+    /// Debug builds assert that `format` parses.
+    pub fn response_format(mut self, format: impl Into<String>) -> Self {
+        let format = format.into();
+        debug_assert_valid_json(&format);
+        self.response_format = Some(format);
+        self
+    }
+
     /// Set the invocation parameters as a JSON string.
     pub fn invocation_parameters(mut self, params: impl Into<String>) -> Self {
         self.invocation_parameters = Some(params.into());
         self
     }
 
+    /// Set whether the response was requested as a stream.
+    pub fn streaming(mut self, streaming: bool) -> Self {
+        self.streaming = Some(streaming);
+        self
+    }
+
     /// Set the input value (e.g., the raw prompt or request body).
     pub fn input_value(mut self, value: impl Into<String>) -> Self {
         self.input_value = Some(value.into());
@@ -160,110 +522,464 @@ impl LlmSpanBuilder {
         self
     }
 
+    /// Set the number of tools offered to the LLM.
+    ///
+    /// Defaults to the number of schemas added via [`tool`](Self::tool). Also
+    /// records `llm.has_tools`, so a count of zero marks a request made
+    /// without tools.
+    pub fn tool_count(mut self, count: i64) -> Self {
+        debug_assert!(count >= 0, "tool_count must be non-negative, got {count}");
+        self.tool_count = Some(count);
+        self
+    }
+
+    /// Set the tool choice mode ("auto", "none", "required", or a tool name).
+    ///
+    /// Also folded into `llm.invocation_parameters` as `tool_choice`.
+    pub fn tool_choice(mut self, choice: impl Into<String>) -> Self {
+        self.tool_choice = Some(choice.into());
+        self
+    }
+
+    /// Set the end-user identifier sent to the provider for abuse monitoring
+    /// (OpenAI's `user` field), as distinct from the application's `user.id`.
+    ///
+    /// Also folded into `llm.invocation_parameters` as `user`.
+    pub fn request_user(mut self, user: impl Into<String>) -> Self {
+        self.request_user = Some(user.into());
+        self
+    }
+
+    /// Set the address of the inference server (e.g., "localhost").
+    pub fn server_address(mut self, address: impl Into<String>) -> Self {
+        self.server_address = Some(address.into());
+        self
+    }
+
+    /// Set the port of the inference server.
+    pub fn server_port(mut self, port: i64) -> Self {
+        self.server_port = Some(port);
+        self
+    }
+
+    /// Set when the request was sent, in unix milliseconds.
+    pub fn request_timestamp(mut self, unix_millis: i64) -> Self {
+        self.request_timestamp = Some(unix_millis);
+        self
+    }
+
+    /// Set when the response was received, in unix milliseconds.
+    pub fn response_timestamp(mut self, unix_millis: i64) -> Self {
+        self.response_timestamp = Some(unix_millis);
+        self
+    }
+
+    /// Set how long prompt construction took, recorded as `llm.prompt_build_ms`.
+    pub fn prompt_build_duration(mut self, duration: Duration) -> Self {
+        self.prompt_build_duration = Some(duration);
+        self
+    }
+
+    /// The OpenInference span kind this builder produces.
+    pub fn kind(&self) -> SpanKind {
+        SpanKind::Llm
+    }
+
+    /// A read-only view of the state set so far, so tests of instrumentation
+    /// wrappers can assert it before [`build`](Self::build).
+    pub fn view(&self) -> LlmSpanBuilderView<'_> {
+        LlmSpanBuilderView {
+            model_name: &self.model_name,
+            provider: self.provider.as_deref(),
+            temperature: self.temperature,
+        }
+    }
+
     /// Build the span.
     ///
     /// Returns a `tracing::Span` with all the configured attributes set via
     /// `OpenTelemetrySpanExt::set_attribute()`.
     pub fn build(self) -> Span {
-        let span_name = format!("llm {}", self.model_name);
-
-        let span = tracing::info_span!("llm", otel.name = %span_name);
+        let _dropped = DroppedAttributeWarning::new();
+        let span_name = kind_span_name(&self.config, self.kind(), &self.model_name)
+            .unwrap_or_else(|| format!("llm {}", self.model_name));
 
-        // -- Core attributes --
-        span.set_attribute(attributes::OPENINFERENCE_SPAN_KIND, SpanKind::Llm.as_str());
-        span.set_attribute(attributes::llm::MODEL_NAME, self.model_name.clone());
+        let span = span_at_level!(self.config.span_level, "llm", otel.name = %span_name);
+        self.record_into(&span);
+        span
+    }
 
-        if let Some(ref provider) = self.provider {
-            span.set_attribute(attributes::llm::PROVIDER, provider.clone());
+    /// Record the configured attributes into `sink` instead of a new span.
+    ///
+    /// [`build`](Self::build) records into the `tracing::Span` it creates;
+    /// tests can target a `MockSpan` (with the `testing` feature) to assert
+    /// attributes without an OTel pipeline.
+    pub fn record_into(mut self, sink: &impl AttributeSink) {
+        self = self.with_llm_defaults();
+        if let Some(emit) = self.emit_gen_ai {
+            self.config.emit_gen_ai_attributes = emit;
         }
-        if let Some(ref system) = self.system {
-            span.set_attribute(attributes::llm::SYSTEM, system.clone());
+        debug_assert!(
+            !self.model_name.is_empty(),
+            "LlmSpanBuilder built without a model name; call model_name() first"
+        );
+        if let Some(prompt) = self.system_prompt.clone() {
+            self.input_messages
+                .insert(0, ("system".to_string(), prompt));
         }
 
-        // -- Invocation parameters --
-        if let Some(ref params) = self.invocation_parameters {
-            if !self.config.hide_llm_invocation_parameters {
-                span.set_attribute(attributes::llm::INVOCATION_PARAMETERS, params.clone());
-            } else {
-                span.set_attribute(attributes::llm::INVOCATION_PARAMETERS, REDACTED);
+        // -- Core attributes --
+        set_span_kind(sink, &self.config, self.kind());
+        self.common.record(sink, &self.config);
+
+        if self.config.emit_openinference_attributes {
+            set_value(
+                sink,
+                &self.config,
+                attributes::llm::MODEL_NAME,
+                self.model_name.clone(),
+            );
+
+            if let Some(ref provider) = self.provider {
+                set_value(
+                    sink,
+                    &self.config,
+                    attributes::llm::PROVIDER,
+                    provider.clone(),
+                );
+            }
+            if let Some(ref system) = self.system {
+                set_value(sink, &self.config, attributes::llm::SYSTEM, system.clone());
+            }
+            if let Some(ref version) = self.model_version {
+                set_value(
+                    sink,
+                    &self.config,
+                    attributes::llm::MODEL_VERSION,
+                    version.clone(),
+                );
+            }
+            if let Some(ref cutoff) = self.knowledge_cutoff {
+                set_value(
+                    sink,
+                    &self.config,
+                    attributes::llm::KNOWLEDGE_CUTOFF,
+                    cutoff.clone(),
+                );
+            }
+            if let Some(ref key) = self.cache_key {
+                set_value(sink, &self.config, attributes::llm::cache::KEY, key.clone());
+            }
+            if let Some(ttl) = self.cache_ttl_seconds {
+                set_value(sink, &self.config, attributes::llm::cache::TTL_SECONDS, ttl);
+            }
+            if let Some(breakpoints) = self.cache_breakpoints {
+                set_value(
+                    sink,
+                    &self.config,
+                    attributes::llm::cache::BREAKPOINTS,
+                    breakpoints,
+                );
+            }
+            if let Some(hit) = self.semantic_cache_hit {
+                set_value(sink, &self.config, attributes::llm::SEMANTIC_CACHE_HIT, hit);
+            }
+            if let Some(similarity) = self.semantic_cache_similarity {
+                set_value(
+                    sink,
+                    &self.config,
+                    attributes::llm::SEMANTIC_CACHE_SIMILARITY,
+                    similarity,
+                );
             }
-        }
 
-        // -- Input value --
-        if let Some(ref input) = self.input_value {
-            if !self.config.hide_inputs {
-                span.set_attribute(attributes::input::VALUE, input.clone());
-            } else {
-                span.set_attribute(attributes::input::VALUE, REDACTED);
+            // -- Invocation parameters --
+            if let Some(params) = self.merged_invocation_parameters() {
+                set_redactable(
+                    sink,
+                    &self.config,
+                    attributes::llm::INVOCATION_PARAMETERS,
+                    params,
+                    self.config.hide_llm_invocation_parameters,
+                );
+            }
+            if let Some(ref user) = self.request_user {
+                set_value(
+                    sink,
+                    &self.config,
+                    attributes::llm::REQUEST_USER,
+                    user.clone(),
+                );
+            }
+            if let Some(streaming) = self.streaming {
+                set_value(sink, &self.config, attributes::llm::IS_STREAMING, streaming);
             }
-        }
 
-        // -- Output value --
-        if let Some(ref output) = self.output_value {
-            if !self.config.hide_outputs {
-                span.set_attribute(attributes::output::VALUE, output.clone());
-            } else {
-                span.set_attribute(attributes::output::VALUE, REDACTED);
+            if let Some(ref schema) = self.output_schema {
+                set_redactable(
+                    sink,
+                    &self.config,
+                    attributes::llm::OUTPUT_SCHEMA,
+                    schema.clone(),
+                    self.config.hide_output_schema,
+                );
+            }
+            if let Some(ref format) = self.response_format {
+                set_value(
+                    sink,
+                    &self.config,
+                    attributes::llm::RESPONSE_FORMAT,
+                    format.clone(),
+                );
+            }
+            if self.response_format.is_some() || self.output_schema.is_some() {
+                set_value(
+                    sink,
+                    &self.config,
+                    attributes::llm::HAS_RESPONSE_FORMAT,
+                    true,
+                );
+            }
+
+            // -- Input value --
+            if let Some(ref input) = self.input_value {
+                set_redactable(
+                    sink,
+                    &self.config,
+                    attributes::input::VALUE,
+                    input.clone(),
+                    self.config.hide_inputs,
+                );
+            } else if self.config.flatten_messages_to_input && !self.input_messages.is_empty() {
+                let flattened = self
+                    .input_messages
+                    .iter()
+                    .map(|(role, content)| format!("{role}: {content}"))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                // The flattened value carries message content, so message
+                // redaction applies to it as well.
+                set_redactable(
+                    sink,
+                    &self.config,
+                    attributes::input::VALUE,
+                    flattened,
+                    self.config.hide_inputs
+                        || self.config.should_hide_input_messages()
+                        || self.config.should_hide_input_text(),
+                );
+            }
+
+            // -- Output value --
+            if let Some(ref output) = self.output_value {
+                set_redactable(
+                    sink,
+                    &self.config,
+                    attributes::output::VALUE,
+                    output.clone(),
+                    self.config.hide_outputs,
+                );
             }
-        }
 
-        // -- Input messages --
-        if !self.input_messages.is_empty() {
-            let hide_messages = self.config.should_hide_input_messages();
-            let hide_text = self.config.should_hide_input_text();
+            if let Some(ref language) = self.language {
+                set_value(
+                    sink,
+                    &self.config,
+                    attributes::input::LANGUAGE,
+                    language.clone(),
+                );
+            }
+            if let Some(turns) = self.conversation_turn_count {
+                set_value(
+                    sink,
+                    &self.config,
+                    attributes::llm::conversation::TURN_COUNT,
+                    turns,
+                );
+            }
 
-            for (i, (role, content)) in self.input_messages.iter().enumerate() {
-                if hide_messages {
-                    span.set_attribute(attributes::llm::input_messages::role(i), REDACTED);
-                    span.set_attribute(attributes::llm::input_messages::content(i), REDACTED);
-                } else {
-                    span.set_attribute(attributes::llm::input_messages::role(i), role.clone());
-                    if hide_text {
-                        span.set_attribute(attributes::llm::input_messages::content(i), REDACTED);
-                    } else {
-                        span.set_attribute(
-                            attributes::llm::input_messages::content(i),
-                            content.clone(),
+            // -- Input messages --
+            if !self.input_messages.is_empty() {
+                let hide_messages = self.config.should_hide_input_messages();
+                let hide_text = self.config.should_hide_input_text();
+
+                // The count is not sensitive, so it survives redaction.
+                set_value(
+                    sink,
+                    &self.config,
+                    attributes::llm::INPUT_MESSAGE_COUNT,
+                    self.input_messages.len() as i64,
+                );
+
+                for (i, (role, content)) in self.input_messages.iter().enumerate() {
+                    if self.config.content_as_events {
+                        // History may replay assistant and tool turns, so
+                        // only `user` messages are user prompts.
+                        let name = match role.as_str() {
+                            "system" => gen_ai::events::SYSTEM_PROMPT,
+                            "user" => gen_ai::events::USER_PROMPT,
+                            "assistant" => gen_ai::events::ASSISTANT_RESPONSE,
+                            "tool" => gen_ai::events::TOOL_MESSAGE,
+                            _ => gen_ai::events::CONTENT,
+                        };
+                        add_message_event(
+                            sink,
+                            &self.config,
+                            name,
+                            i,
+                            (role, hide_messages),
+                            (content, hide_messages || hide_text),
                         );
+                        continue;
                     }
+                    set_redactable(
+                        sink,
+                        &self.config,
+                        attributes::llm::input_messages::role(i),
+                        role.clone(),
+                        hide_messages,
+                    );
+                    set_redactable(
+                        sink,
+                        &self.config,
+                        attributes::llm::input_messages::content(i),
+                        content.clone(),
+                        hide_messages || hide_text,
+                    );
                 }
             }
-        }
 
-        // -- Tools --
-        for (i, schema) in self.tools.iter().enumerate() {
-            span.set_attribute(attributes::llm::tools::json_schema(i), schema.clone());
+            // -- Tools --
+            for (i, schema) in self.tools.iter().enumerate() {
+                set_value(
+                    sink,
+                    &self.config,
+                    attributes::llm::tools::json_schema(i),
+                    schema.clone(),
+                );
+            }
+            let tool_count = self
+                .tool_count
+                .or_else(|| (!self.tools.is_empty()).then_some(self.tools.len() as i64));
+            // `llm.has_tools` is only false when a zero tool count was set.
+            if let Some(count) = tool_count {
+                set_value(sink, &self.config, attributes::llm::TOOL_COUNT, count);
+                set_value(sink, &self.config, attributes::llm::HAS_TOOLS, count > 0);
+            }
+            if let Some(ref choice) = self.tool_choice {
+                set_value(
+                    sink,
+                    &self.config,
+                    attributes::llm::TOOL_CHOICE,
+                    choice.clone(),
+                );
+            }
+
+            // -- Timestamps --
+            if let Some(ts) = self.request_timestamp {
+                set_value(sink, &self.config, attributes::llm::request::TIMESTAMP, ts);
+            }
+            if let Some(ts) = self.response_timestamp {
+                set_value(sink, &self.config, attributes::llm::response::TIMESTAMP, ts);
+            }
+            if let Some(duration) = self.prompt_build_duration {
+                set_value(
+                    sink,
+                    &self.config,
+                    attributes::llm::PROMPT_BUILD_MS,
+                    duration_millis(duration),
+                );
+            }
         }
 
         // -- OTel GenAI attributes --
         if self.config.emit_gen_ai_attributes {
-            span.set_attribute(gen_ai::request::MODEL, self.model_name.clone());
+            set_value(
+                sink,
+                &self.config,
+                gen_ai::request::MODEL,
+                self.model_name.clone(),
+            );
             if let Some(ref provider) = self.provider {
-                span.set_attribute(gen_ai::PROVIDER_NAME, provider.clone());
+                set_value(sink, &self.config, gen_ai::PROVIDER_NAME, provider.clone());
             }
             if let Some(ref system) = self.system {
-                span.set_attribute(gen_ai::SYSTEM, system.clone());
+                set_value(sink, &self.config, gen_ai::SYSTEM, system.clone());
             }
             if let Some(temp) = self.temperature {
-                span.set_attribute(gen_ai::request::TEMPERATURE, temp);
+                set_value(sink, &self.config, gen_ai::request::TEMPERATURE, temp);
             }
             if let Some(top_p) = self.top_p {
-                span.set_attribute(gen_ai::request::TOP_P, top_p);
+                set_value(sink, &self.config, gen_ai::request::TOP_P, top_p);
             }
             if let Some(top_k) = self.top_k {
-                span.set_attribute(gen_ai::request::TOP_K, top_k);
+                set_value(sink, &self.config, gen_ai::request::TOP_K, top_k);
             }
             if let Some(max_tokens) = self.max_tokens {
-                span.set_attribute(gen_ai::request::MAX_TOKENS, max_tokens);
+                set_value(sink, &self.config, gen_ai::request::MAX_TOKENS, max_tokens);
             }
             if let Some(freq) = self.frequency_penalty {
-                span.set_attribute(gen_ai::request::FREQUENCY_PENALTY, freq);
+                set_value(sink, &self.config, gen_ai::request::FREQUENCY_PENALTY, freq);
             }
             if let Some(pres) = self.presence_penalty {
-                span.set_attribute(gen_ai::request::PRESENCE_PENALTY, pres);
+                set_value(sink, &self.config, gen_ai::request::PRESENCE_PENALTY, pres);
+            }
+            if let Some(ref address) = self.server_address {
+                set_value(sink, &self.config, gen_ai::server::ADDRESS, address.clone());
+            }
+            if let Some(port) = self.server_port {
+                set_value(sink, &self.config, gen_ai::server::PORT, port);
             }
+            if let Some(ref prompt) = self.system_prompt {
+                set_redactable(
+                    sink,
+                    &self.config,
+                    gen_ai::request::SYSTEM_INSTRUCTIONS,
+                    prompt.clone(),
+                    self.config.should_hide_input_text(),
+                );
+            }
+        }
+    }
+
+    /// The invocation parameters JSON with builder-level parameters (such as
+    /// `tool_choice`) folded in.
+    ///
+    /// If the user-supplied parameters are not a JSON object they are returned
+    /// unchanged.
+    fn merged_invocation_parameters(&self) -> Option<String> {
+        let mut extra = serde_json::Map::new();
+        if let Some(ref choice) = self.tool_choice {
+            extra.insert("tool_choice".to_string(), choice.clone().into());
+        }
+        if let Some(ref user) = self.request_user {
+            extra.insert("user".to_string(), user.clone().into());
+        }
+        if extra.is_empty() {
+            return self.invocation_parameters.clone();
         }
 
+        let mut params = match self.invocation_parameters.as_deref() {
+            Some(raw) => match serde_json::from_str(raw) {
+                Ok(serde_json::Value::Object(map)) => map,
+                _ => return self.invocation_parameters.clone(),
+            },
+            None => serde_json::Map::new(),
+        };
+        params.extend(extra);
+        Some(serde_json::Value::Object(params).to_string())
+    }
+
+    /// Build the span, then run `f` on it to record post-build attributes.
+    ///
+    /// ```rust,ignore
+    /// let span = LlmSpanBuilder::new("gpt-4").build_with(|span| {
+    ///     record_token_usage(span, 100, 50);
+    /// });
+    /// ```
+    pub fn build_with(self, f: impl FnOnce(&Span)) -> Span {
+        let span = self.build();
+        f(&span);
         span
     }
 }
@@ -276,8 +992,15 @@ impl LlmSpanBuilder {
 #[derive(Debug)]
 pub struct EmbeddingSpanBuilder {
     model_name: String,
+    provider: Option<String>,
     texts: Vec<String>,
     input_value: Option<String>,
+    encoding_formats: Vec<String>,
+    input_tokens: Option<i64>,
+    dimensions: Option<i64>,
+    distance_metric: Option<String>,
+    common: CommonAttrs,
+    emit_gen_ai: Option<bool>,
     config: TraceConfig,
 }
 
@@ -286,8 +1009,15 @@ impl EmbeddingSpanBuilder {
     pub fn new(model_name: impl Into<String>) -> Self {
         Self {
             model_name: model_name.into(),
+            provider: None,
             texts: Vec::new(),
             input_value: None,
+            encoding_formats: Vec::new(),
+            input_tokens: None,
+            dimensions: None,
+            distance_metric: None,
+            common: CommonAttrs::default(),
+            emit_gen_ai: None,
             config: TraceConfig::default(),
         }
     }
@@ -298,6 +1028,20 @@ impl EmbeddingSpanBuilder {
         self
     }
 
+    /// Override `emit_gen_ai_attributes` from the config for this span only.
+    pub fn emit_gen_ai(mut self, emit: bool) -> Self {
+        self.emit_gen_ai = Some(emit);
+        self
+    }
+
+    common_attr_setters!();
+
+    /// Set the embedding provider (e.g., "openai", "cohere").
+    pub fn provider(mut self, provider: impl Into<String>) -> Self {
+        self.provider = Some(provider.into());
+        self
+    }
+
     /// Add a text to embed.
     pub fn text(mut self, text: impl Into<String>) -> Self {
         self.texts.push(text.into());
@@ -316,38 +1060,172 @@ impl EmbeddingSpanBuilder {
         self
     }
 
+    /// Add a requested encoding format (e.g., "float", "base64").
+    pub fn encoding_format(mut self, format: impl Into<String>) -> Self {
+        self.encoding_formats.push(format.into());
+        self
+    }
+
+    /// Set the number of input tokens reported by the embedding API.
+    ///
+    /// Use [`record_embedding_token_usage`] when the count is only known
+    /// after the call.
+    pub fn input_tokens(mut self, tokens: i64) -> Self {
+        self.input_tokens = Some(tokens);
+        self
+    }
+
+    /// Set the embedding dimensionality explicitly.
+    ///
+    /// Only needed when vectors aren't recorded: with the `registry` feature,
+    /// [`record_embedding_vector`] derives `embedding.dimensions` from the
+    /// first vector's length unless it was set here.
+    pub fn dimensions(mut self, dimensions: i64) -> Self {
+        self.dimensions = Some(dimensions);
+        self
+    }
+
+    /// Set the distance metric the vectors are compared with (e.g., "cosine",
+    /// "dot", "euclidean").
+    pub fn distance_metric(mut self, metric: impl Into<String>) -> Self {
+        self.distance_metric = Some(metric.into());
+        self
+    }
+
+    /// The OpenInference span kind this builder produces.
+    pub fn kind(&self) -> SpanKind {
+        SpanKind::Embedding
+    }
+
     /// Build the span.
     pub fn build(self) -> Span {
-        let span_name = format!("embedding {}", self.model_name);
+        let _dropped = DroppedAttributeWarning::new();
+        let span_name = kind_span_name(&self.config, self.kind(), &self.model_name)
+            .unwrap_or_else(|| format!("embedding {}", self.model_name));
 
-        let span = tracing::info_span!("embedding", otel.name = %span_name);
+        let span = span_at_level!(self.config.span_level, "embedding", otel.name = %span_name);
 
-        span.set_attribute(
-            attributes::OPENINFERENCE_SPAN_KIND,
-            SpanKind::Embedding.as_str(),
-        );
-        span.set_attribute(attributes::embedding::MODEL_NAME, self.model_name.clone());
+        let explicit_dimensions =
+            self.dimensions.is_some() && self.config.emit_openinference_attributes;
+        self.record_into(&span);
+        if explicit_dimensions {
+            update_span_flags(&span, |flags| flags.dimensions = true);
+        }
+        span
+    }
 
-        // Embedding texts
-        let hide_text = self.config.hide_embeddings_text;
-        for (i, text) in self.texts.iter().enumerate() {
-            if hide_text {
-                span.set_attribute(attributes::embedding::embeddings::text(i), REDACTED);
-            } else {
-                span.set_attribute(attributes::embedding::embeddings::text(i), text.clone());
-            }
+    /// Record the configured attributes into `sink` instead of a new span.
+    ///
+    /// See [`LlmSpanBuilder::record_into`]. Only [`build`](Self::build) marks
+    /// explicit [`dimensions`](Self::dimensions) so later vectors don't
+    /// overwrite them, since that state lives on the span.
+    pub fn record_into(mut self, sink: &impl AttributeSink) {
+        if let Some(emit) = self.emit_gen_ai {
+            self.config.emit_gen_ai_attributes = emit;
         }
+        set_span_kind(sink, &self.config, self.kind());
+        self.common.record(sink, &self.config);
+
+        if self.config.emit_openinference_attributes {
+            set_value(
+                sink,
+                &self.config,
+                attributes::embedding::MODEL_NAME,
+                self.model_name.clone(),
+            );
+            if let Some(ref provider) = self.provider {
+                set_value(
+                    sink,
+                    &self.config,
+                    attributes::embedding::PROVIDER,
+                    provider.clone(),
+                );
+            }
 
-        // Input value
-        if let Some(ref input) = self.input_value {
-            if !self.config.hide_inputs {
-                span.set_attribute(attributes::input::VALUE, input.clone());
-            } else {
-                span.set_attribute(attributes::input::VALUE, REDACTED);
+            // Embedding texts
+            let hide_text = self.config.hide_embeddings_text;
+            for (i, text) in self.texts.iter().enumerate() {
+                set_redactable(
+                    sink,
+                    &self.config,
+                    attributes::embedding::embeddings::text(i),
+                    text.clone(),
+                    hide_text,
+                );
             }
-        }
 
-        span
+            // Input value
+            if let Some(ref input) = self.input_value {
+                set_redactable(
+                    sink,
+                    &self.config,
+                    attributes::input::VALUE,
+                    input.clone(),
+                    self.config.hide_inputs,
+                );
+            }
+
+            // Encoding formats
+            if !self.encoding_formats.is_empty() {
+                set_value(
+                    sink,
+                    &self.config,
+                    attributes::embedding::ENCODING_FORMATS,
+                    string_array(&self.encoding_formats),
+                );
+            }
+
+            if let Some(tokens) = self.input_tokens {
+                set_value(
+                    sink,
+                    &self.config,
+                    attributes::llm::token_count::PROMPT,
+                    tokens,
+                );
+                set_value(
+                    sink,
+                    &self.config,
+                    attributes::llm::token_count::TOTAL,
+                    tokens,
+                );
+            }
+
+            if let Some(dimensions) = self.dimensions {
+                set_value(
+                    sink,
+                    &self.config,
+                    attributes::embedding::DIMENSIONS,
+                    dimensions,
+                );
+            }
+
+            if let Some(ref metric) = self.distance_metric {
+                set_value(
+                    sink,
+                    &self.config,
+                    attributes::embedding::DISTANCE_METRIC,
+                    metric.clone(),
+                );
+            }
+        }
+
+        // -- OTel GenAI attributes --
+        if self.config.emit_gen_ai_attributes {
+            if let Some(ref provider) = self.provider {
+                set_value(sink, &self.config, gen_ai::PROVIDER_NAME, provider.clone());
+            }
+            if !self.encoding_formats.is_empty() {
+                set_value(
+                    sink,
+                    &self.config,
+                    gen_ai::request::ENCODING_FORMATS,
+                    string_array(&self.encoding_formats),
+                );
+            }
+            if let Some(tokens) = self.input_tokens {
+                set_value(sink, &self.config, gen_ai::usage::INPUT_TOKENS, tokens);
+            }
+        }
     }
 }
 
@@ -359,10 +1237,13 @@ impl EmbeddingSpanBuilder {
 #[derive(Debug)]
 pub struct ChainSpanBuilder {
     name: String,
-    input_value: Option<String>,
+    input_value: Option<Value>,
     input_mime_type: Option<String>,
+    language: Option<String>,
     output_value: Option<String>,
     output_mime_type: Option<String>,
+    chain_type: Option<String>,
+    common: CommonAttrs,
     config: TraceConfig,
 }
 
@@ -373,8 +1254,11 @@ impl ChainSpanBuilder {
             name: name.into(),
             input_value: None,
             input_mime_type: None,
+            language: None,
             output_value: None,
             output_mime_type: None,
+            chain_type: None,
+            common: CommonAttrs::default(),
             config: TraceConfig::default(),
         }
     }
@@ -385,8 +1269,19 @@ impl ChainSpanBuilder {
         self
     }
 
+    common_attr_setters!();
+
     /// Set the input value.
     pub fn input(mut self, value: impl Into<String>) -> Self {
+        self.input_value = Some(Value::String(value.into().into()));
+        self
+    }
+
+    /// Set the input value as a typed OTel value (e.g., an `i64` or `bool`).
+    ///
+    /// Non-string values are recorded with their type preserved, and
+    /// `input.mime_type` defaults to `application/json` unless set explicitly.
+    pub fn input_value(mut self, value: impl Into<Value>) -> Self {
         self.input_value = Some(value.into());
         self
     }
@@ -397,6 +1292,12 @@ impl ChainSpanBuilder {
         self
     }
 
+    /// Set the detected language or locale of the input (e.g., "en").
+    pub fn language(mut self, language: impl Into<String>) -> Self {
+        self.language = Some(language.into());
+        self
+    }
+
     /// Set the output value.
     pub fn output(mut self, value: impl Into<String>) -> Self {
         self.output_value = Some(value.into());
@@ -409,38 +1310,91 @@ impl ChainSpanBuilder {
         self
     }
 
+    /// Set the type of chain (e.g., "rag", "router", "map_reduce"), for
+    /// filtering.
+    pub fn chain_type(mut self, chain_type: impl Into<String>) -> Self {
+        self.chain_type = Some(chain_type.into());
+        self
+    }
+
+    /// The OpenInference span kind this builder produces.
+    pub fn kind(&self) -> SpanKind {
+        SpanKind::Chain
+    }
+
     /// Build the span.
     pub fn build(self) -> Span {
-        let span = tracing::info_span!("chain", otel.name = %self.name);
+        let _dropped = DroppedAttributeWarning::new();
+        let span_name = kind_span_name(&self.config, self.kind(), &self.name)
+            .unwrap_or_else(|| self.name.clone());
 
-        span.set_attribute(
-            attributes::OPENINFERENCE_SPAN_KIND,
-            SpanKind::Chain.as_str(),
-        );
+        let span = span_at_level!(self.config.span_level, "chain", otel.name = %span_name);
+
+        self.record_into(&span);
+        span
+    }
 
-        if let Some(ref input) = self.input_value {
-            if !self.config.hide_inputs {
-                span.set_attribute(attributes::input::VALUE, input.clone());
-            } else {
-                span.set_attribute(attributes::input::VALUE, REDACTED);
+    /// Record the configured attributes into `sink` instead of a new span.
+    ///
+    /// See [`LlmSpanBuilder::record_into`].
+    pub fn record_into(self, sink: &impl AttributeSink) {
+        set_span_kind(sink, &self.config, self.kind());
+        self.common.record(sink, &self.config);
+
+        if self.config.emit_openinference_attributes {
+            if let Some(ref input) = self.input_value {
+                set_redactable(
+                    sink,
+                    &self.config,
+                    attributes::input::VALUE,
+                    input.clone(),
+                    self.config.hide_inputs,
+                );
+            }
+            let input_mime_type = match (&self.input_mime_type, &self.input_value) {
+                (Some(mime_type), _) => Some(mime_type.clone()),
+                (None, Some(Value::String(_))) | (None, None) => None,
+                (None, Some(_)) => Some("application/json".to_string()),
+            };
+            if let Some(mime_type) = input_mime_type {
+                set_value(sink, &self.config, attributes::input::MIME_TYPE, mime_type);
+            }
+            if let Some(ref language) = self.language {
+                set_value(
+                    sink,
+                    &self.config,
+                    attributes::input::LANGUAGE,
+                    language.clone(),
+                );
             }
-        }
-        if let Some(ref mime_type) = self.input_mime_type {
-            span.set_attribute(attributes::input::MIME_TYPE, mime_type.clone());
-        }
 
-        if let Some(ref output) = self.output_value {
-            if !self.config.hide_outputs {
-                span.set_attribute(attributes::output::VALUE, output.clone());
-            } else {
-                span.set_attribute(attributes::output::VALUE, REDACTED);
+            if let Some(ref output) = self.output_value {
+                set_redactable(
+                    sink,
+                    &self.config,
+                    attributes::output::VALUE,
+                    output.clone(),
+                    self.config.hide_outputs,
+                );
+            }
+            if let Some(ref mime_type) = self.output_mime_type {
+                set_value(
+                    sink,
+                    &self.config,
+                    attributes::output::MIME_TYPE,
+                    mime_type.clone(),
+                );
             }
-        }
-        if let Some(ref mime_type) = self.output_mime_type {
-            span.set_attribute(attributes::output::MIME_TYPE, mime_type.clone());
-        }
 
-        span
+            if let Some(ref chain_type) = self.chain_type {
+                set_value(
+                    sink,
+                    &self.config,
+                    attributes::chain::TYPE,
+                    chain_type.clone(),
+                );
+            }
+        }
     }
 }
 
@@ -456,6 +1410,8 @@ pub struct ToolSpanBuilder {
     parameters: Option<String>,
     input_value: Option<String>,
     output_value: Option<String>,
+    success: Option<bool>,
+    common: CommonAttrs,
     config: TraceConfig,
 }
 
@@ -468,6 +1424,8 @@ impl ToolSpanBuilder {
             parameters: None,
             input_value: None,
             output_value: None,
+            success: None,
+            common: CommonAttrs::default(),
             config: TraceConfig::default(),
         }
     }
@@ -478,6 +1436,8 @@ impl ToolSpanBuilder {
         self
     }
 
+    common_attr_setters!();
+
     /// Set the tool description.
     pub fn description(mut self, description: impl Into<String>) -> Self {
         self.description = Some(description.into());
@@ -485,6 +1445,9 @@ impl ToolSpanBuilder {
     }
 
     /// Set the tool parameters (JSON string).
+    ///
+    /// Redacted when `should_hide_tool_parameters()` is set, since arguments
+    /// often carry user data.
     pub fn parameters(mut self, parameters: impl Into<String>) -> Self {
         self.parameters = Some(parameters.into());
         self
@@ -502,38 +1465,85 @@ impl ToolSpanBuilder {
         self
     }
 
+    /// Set whether the tool executed successfully.
+    pub fn success(mut self, success: bool) -> Self {
+        self.success = Some(success);
+        self
+    }
+
+    /// The OpenInference span kind this builder produces.
+    pub fn kind(&self) -> SpanKind {
+        SpanKind::Tool
+    }
+
     /// Build the span.
     pub fn build(self) -> Span {
-        let span_name = format!("tool {}", self.name);
+        let _dropped = DroppedAttributeWarning::new();
+        let span_name = kind_span_name(&self.config, self.kind(), &self.name)
+            .unwrap_or_else(|| format!("tool {}", self.name));
 
-        let span = tracing::info_span!("tool", otel.name = %span_name);
+        let span = span_at_level!(self.config.span_level, "tool", otel.name = %span_name);
 
-        span.set_attribute(attributes::OPENINFERENCE_SPAN_KIND, SpanKind::Tool.as_str());
-        span.set_attribute(attributes::tool::NAME, self.name.clone());
+        self.record_into(&span);
+        span
+    }
 
-        if let Some(ref desc) = self.description {
-            span.set_attribute(attributes::tool::DESCRIPTION, desc.clone());
-        }
-        if let Some(ref params) = self.parameters {
-            span.set_attribute(attributes::tool::PARAMETERS, params.clone());
-        }
+    /// Record the configured attributes into `sink` instead of a new span.
+    ///
+    /// See [`LlmSpanBuilder::record_into`].
+    pub fn record_into(self, sink: &impl AttributeSink) {
+        set_span_kind(sink, &self.config, self.kind());
+        self.common.record(sink, &self.config);
+
+        if self.config.emit_openinference_attributes {
+            set_value(
+                sink,
+                &self.config,
+                attributes::tool::NAME,
+                self.name.clone(),
+            );
 
-        if let Some(ref input) = self.input_value {
-            if !self.config.hide_inputs {
-                span.set_attribute(attributes::input::VALUE, input.clone());
-            } else {
-                span.set_attribute(attributes::input::VALUE, REDACTED);
+            if let Some(ref desc) = self.description {
+                set_value(
+                    sink,
+                    &self.config,
+                    attributes::tool::DESCRIPTION,
+                    desc.clone(),
+                );
             }
-        }
-        if let Some(ref output) = self.output_value {
-            if !self.config.hide_outputs {
-                span.set_attribute(attributes::output::VALUE, output.clone());
-            } else {
-                span.set_attribute(attributes::output::VALUE, REDACTED);
+            if let Some(ref params) = self.parameters {
+                set_redactable(
+                    sink,
+                    &self.config,
+                    attributes::tool::PARAMETERS,
+                    params.clone(),
+                    self.config.should_hide_tool_parameters(),
+                );
             }
-        }
 
-        span
+            if let Some(ref input) = self.input_value {
+                set_redactable(
+                    sink,
+                    &self.config,
+                    attributes::input::VALUE,
+                    input.clone(),
+                    self.config.hide_inputs,
+                );
+            }
+            if let Some(ref output) = self.output_value {
+                set_redactable(
+                    sink,
+                    &self.config,
+                    attributes::output::VALUE,
+                    output.clone(),
+                    self.config.hide_outputs,
+                );
+            }
+
+            if let Some(success) = self.success {
+                set_value(sink, &self.config, attributes::tool::SUCCESS, success);
+            }
+        }
     }
 }
 
@@ -547,6 +1557,9 @@ pub struct RetrieverSpanBuilder {
     name: String,
     query: Option<String>,
     top_k: Option<i64>,
+    distance_metric: Option<String>,
+    query_embedding: Option<Vec<f32>>,
+    common: CommonAttrs,
     config: TraceConfig,
 }
 
@@ -557,6 +1570,9 @@ impl RetrieverSpanBuilder {
             name: name.into(),
             query: None,
             top_k: None,
+            distance_metric: None,
+            query_embedding: None,
+            common: CommonAttrs::default(),
             config: TraceConfig::default(),
         }
     }
@@ -567,6 +1583,8 @@ impl RetrieverSpanBuilder {
         self
     }
 
+    common_attr_setters!();
+
     /// Set the retrieval query.
     pub fn query(mut self, query: impl Into<String>) -> Self {
         self.query = Some(query.into());
@@ -579,26 +1597,74 @@ impl RetrieverSpanBuilder {
         self
     }
 
+    /// Set the distance metric used for similarity search (e.g., "cosine",
+    /// "dot", "euclidean").
+    pub fn distance_metric(mut self, metric: impl Into<String>) -> Self {
+        self.distance_metric = Some(metric.into());
+        self
+    }
+
+    /// Set the embedding vector of the retrieval query.
+    ///
+    /// Encoded per `embedding_vector_encoding` and redacted when
+    /// `should_hide_embedding_vectors()` is set.
+    pub fn query_embedding(mut self, vector: Vec<f32>) -> Self {
+        self.query_embedding = Some(vector);
+        self
+    }
+
+    /// The OpenInference span kind this builder produces.
+    pub fn kind(&self) -> SpanKind {
+        SpanKind::Retriever
+    }
+
     /// Build the span.
     pub fn build(self) -> Span {
-        let span_name = format!("retriever {}", self.name);
+        let _dropped = DroppedAttributeWarning::new();
+        let span_name = kind_span_name(&self.config, self.kind(), &self.name)
+            .unwrap_or_else(|| format!("retriever {}", self.name));
 
-        let span = tracing::info_span!("retriever", otel.name = %span_name);
+        let span = span_at_level!(self.config.span_level, "retriever", otel.name = %span_name);
 
-        span.set_attribute(
-            attributes::OPENINFERENCE_SPAN_KIND,
-            SpanKind::Retriever.as_str(),
-        );
+        self.record_into(&span);
+        span
+    }
 
-        if let Some(ref query) = self.query {
-            if !self.config.hide_inputs {
-                span.set_attribute(attributes::input::VALUE, query.clone());
-            } else {
-                span.set_attribute(attributes::input::VALUE, REDACTED);
+    /// Record the configured attributes into `sink` instead of a new span.
+    ///
+    /// See [`LlmSpanBuilder::record_into`].
+    pub fn record_into(self, sink: &impl AttributeSink) {
+        set_span_kind(sink, &self.config, self.kind());
+        self.common.record(sink, &self.config);
+
+        if self.config.emit_openinference_attributes {
+            if let Some(ref query) = self.query {
+                set_redactable(
+                    sink,
+                    &self.config,
+                    attributes::input::VALUE,
+                    query.clone(),
+                    self.config.hide_inputs,
+                );
             }
-        }
 
-        span
+            if let Some(ref metric) = self.distance_metric {
+                set_value(
+                    sink,
+                    &self.config,
+                    attributes::retrieval::DISTANCE_METRIC,
+                    metric.clone(),
+                );
+            }
+            if let Some(ref vector) = self.query_embedding {
+                set_value(
+                    sink,
+                    &self.config,
+                    attributes::retrieval::QUERY_EMBEDDING,
+                    embedding_vector_value(vector, &self.config),
+                );
+            }
+        }
     }
 }
 
@@ -610,8 +1676,18 @@ impl RetrieverSpanBuilder {
 #[derive(Debug)]
 pub struct AgentSpanBuilder {
     name: String,
+    description: Option<String>,
     input_value: Option<String>,
     output_value: Option<String>,
+    is_final_answer: bool,
+    goal: Option<String>,
+    plan: Option<String>,
+    state: Option<String>,
+    parent_agent: Option<String>,
+    delegated_to: Option<String>,
+    invoked_tools: Vec<String>,
+    emit_gen_ai: Option<bool>,
+    common: CommonAttrs,
     config: TraceConfig,
 }
 
@@ -620,8 +1696,18 @@ impl AgentSpanBuilder {
     pub fn new(name: impl Into<String>) -> Self {
         Self {
             name: name.into(),
+            description: None,
             input_value: None,
             output_value: None,
+            is_final_answer: false,
+            goal: None,
+            plan: None,
+            state: None,
+            parent_agent: None,
+            delegated_to: None,
+            invoked_tools: Vec::new(),
+            emit_gen_ai: None,
+            common: CommonAttrs::default(),
             config: TraceConfig::default(),
         }
     }
@@ -632,6 +1718,14 @@ impl AgentSpanBuilder {
         self
     }
 
+    /// Override `emit_gen_ai_attributes` from the config for this span only.
+    pub fn emit_gen_ai(mut self, emit: bool) -> Self {
+        self.emit_gen_ai = Some(emit);
+        self
+    }
+
+    common_attr_setters!();
+
     /// Set the input value.
     pub fn input_value(mut self, value: impl Into<String>) -> Self {
         self.input_value = Some(value.into());
@@ -644,34 +1738,169 @@ impl AgentSpanBuilder {
         self
     }
 
+    /// Set the agent's final answer.
+    ///
+    /// Recorded as `output.value` (redacted when outputs are hidden) with
+    /// `agent.final` set to `true`, distinguishing it from intermediate
+    /// outputs. Replaces any [`output_value`](Self::output_value).
+    pub fn final_answer(mut self, text: impl Into<String>) -> Self {
+        self.output_value = Some(text.into());
+        self.is_final_answer = true;
+        self
+    }
+
+    /// Set a description of the agent's purpose.
+    ///
+    /// Recorded as `gen_ai.agent.description` when GenAI attributes are
+    /// emitted.
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set the agent's goal.
+    pub fn goal(mut self, goal: impl Into<String>) -> Self {
+        self.goal = Some(goal.into());
+        self
+    }
+
+    /// Set the agent's plan.
+    pub fn plan(mut self, plan: impl Into<String>) -> Self {
+        self.plan = Some(plan.into());
+        self
+    }
+
+    /// Set the agent's serialized state.
+    pub fn state(mut self, state: impl Into<String>) -> Self {
+        self.state = Some(state.into());
+        self
+    }
+
+    /// Set the name of the agent that delegated to this one.
+    pub fn parent_agent(mut self, name: impl Into<String>) -> Self {
+        self.parent_agent = Some(name.into());
+        self
+    }
+
+    /// Set the name of the sub-agent this agent delegated to.
+    pub fn delegated_to(mut self, agent_name: impl Into<String>) -> Self {
+        self.delegated_to = Some(agent_name.into());
+        self
+    }
+
+    /// Set the names of the tools the agent actually invoked, in order.
+    pub fn invoked_tools(mut self, tools: Vec<String>) -> Self {
+        self.invoked_tools = tools;
+        self
+    }
+
+    /// The OpenInference span kind this builder produces.
+    pub fn kind(&self) -> SpanKind {
+        SpanKind::Agent
+    }
+
     /// Build the span.
     pub fn build(self) -> Span {
-        let span_name = format!("agent {}", self.name);
+        let _dropped = DroppedAttributeWarning::new();
+        let span_name = kind_span_name(&self.config, self.kind(), &self.name)
+            .unwrap_or_else(|| format!("agent {}", self.name));
 
-        let span = tracing::info_span!("agent", otel.name = %span_name);
+        let span = span_at_level!(self.config.span_level, "agent", otel.name = %span_name);
 
-        span.set_attribute(
-            attributes::OPENINFERENCE_SPAN_KIND,
-            SpanKind::Agent.as_str(),
-        );
-        span.set_attribute(attributes::agent::NAME, self.name.clone());
+        self.record_into(&span);
+        span
+    }
 
-        if let Some(ref input) = self.input_value {
-            if !self.config.hide_inputs {
-                span.set_attribute(attributes::input::VALUE, input.clone());
-            } else {
-                span.set_attribute(attributes::input::VALUE, REDACTED);
-            }
+    /// Record the configured attributes into `sink` instead of a new span.
+    ///
+    /// See [`LlmSpanBuilder::record_into`].
+    pub fn record_into(mut self, sink: &impl AttributeSink) {
+        if let Some(emit) = self.emit_gen_ai {
+            self.config.emit_gen_ai_attributes = emit;
         }
-        if let Some(ref output) = self.output_value {
-            if !self.config.hide_outputs {
-                span.set_attribute(attributes::output::VALUE, output.clone());
-            } else {
-                span.set_attribute(attributes::output::VALUE, REDACTED);
+        set_span_kind(sink, &self.config, self.kind());
+        self.common.record(sink, &self.config);
+
+        if self.config.emit_openinference_attributes {
+            set_value(
+                sink,
+                &self.config,
+                attributes::agent::NAME,
+                self.name.clone(),
+            );
+
+            if let Some(ref input) = self.input_value {
+                set_redactable(
+                    sink,
+                    &self.config,
+                    attributes::input::VALUE,
+                    input.clone(),
+                    self.config.hide_inputs,
+                );
+            }
+            if let Some(ref output) = self.output_value {
+                set_redactable(
+                    sink,
+                    &self.config,
+                    attributes::output::VALUE,
+                    output.clone(),
+                    self.config.hide_outputs,
+                );
+            }
+            if self.is_final_answer {
+                set_value(sink, &self.config, attributes::agent::FINAL, true);
+            }
+
+            // Goal, plan, and state may contain sensitive reasoning.
+            let hide_state = self.config.should_hide_agent_state();
+            for (key, value) in [
+                (attributes::agent::GOAL, &self.goal),
+                (attributes::agent::PLAN, &self.plan),
+                (attributes::agent::STATE, &self.state),
+            ] {
+                if let Some(value) = value {
+                    set_redactable(sink, &self.config, key, value.clone(), hide_state);
+                }
+            }
+
+            if let Some(ref parent) = self.parent_agent {
+                set_value(
+                    sink,
+                    &self.config,
+                    attributes::agent::PARENT,
+                    parent.clone(),
+                );
+            }
+            if let Some(ref delegate) = self.delegated_to {
+                set_value(
+                    sink,
+                    &self.config,
+                    attributes::agent::DELEGATED_TO,
+                    delegate.clone(),
+                );
+            }
+            if !self.invoked_tools.is_empty() {
+                set_value(
+                    sink,
+                    &self.config,
+                    attributes::agent::INVOKED_TOOLS,
+                    string_array(&self.invoked_tools),
+                );
             }
         }
 
-        span
+        // -- OTel GenAI attributes --
+        if self.config.emit_gen_ai_attributes {
+            set_value(sink, &self.config, gen_ai::agent::NAME, self.name.clone());
+            if let Some(ref description) = self.description {
+                set_value(
+                    sink,
+                    &self.config,
+                    gen_ai::agent::DESCRIPTION,
+                    description.clone(),
+                );
+            }
+        }
     }
 }
 
@@ -688,12 +1917,19 @@ pub struct Document {
 }
 
 /// Builder for reranker spans.
+///
+/// Reranking has no GenAI operation name, so only the model-level GenAI
+/// attributes apply: `gen_ai.request.model` and, when set via
+/// [`input_tokens`](Self::input_tokens), `gen_ai.usage.input_tokens`.
 #[derive(Debug)]
 pub struct RerankerSpanBuilder {
     model_name: String,
     query: Option<String>,
     top_k: Option<i64>,
     input_documents: Vec<Document>,
+    input_tokens: Option<i64>,
+    common: CommonAttrs,
+    emit_gen_ai: Option<bool>,
     config: TraceConfig,
 }
 
@@ -705,6 +1941,9 @@ impl RerankerSpanBuilder {
             query: None,
             top_k: None,
             input_documents: Vec::new(),
+            input_tokens: None,
+            common: CommonAttrs::default(),
+            emit_gen_ai: None,
             config: TraceConfig::default(),
         }
     }
@@ -715,6 +1954,14 @@ impl RerankerSpanBuilder {
         self
     }
 
+    /// Override `emit_gen_ai_attributes` from the config for this span only.
+    pub fn emit_gen_ai(mut self, emit: bool) -> Self {
+        self.emit_gen_ai = Some(emit);
+        self
+    }
+
+    common_attr_setters!();
+
     /// Set the reranking query.
     pub fn query(mut self, query: impl Into<String>) -> Self {
         self.query = Some(query.into());
@@ -733,325 +1980,2091 @@ impl RerankerSpanBuilder {
         self
     }
 
+    /// Set the number of input tokens reported by the reranking API.
+    pub fn input_tokens(mut self, tokens: i64) -> Self {
+        self.input_tokens = Some(tokens);
+        self
+    }
+
+    /// The OpenInference span kind this builder produces.
+    pub fn kind(&self) -> SpanKind {
+        SpanKind::Reranker
+    }
+
     /// Build the span.
     pub fn build(self) -> Span {
-        let span_name = format!("reranker {}", self.model_name);
+        let _dropped = DroppedAttributeWarning::new();
+        let span_name = kind_span_name(&self.config, self.kind(), &self.model_name)
+            .unwrap_or_else(|| format!("reranker {}", self.model_name));
 
-        let span = tracing::info_span!("reranker", otel.name = %span_name);
+        let span = span_at_level!(self.config.span_level, "reranker", otel.name = %span_name);
 
-        span.set_attribute(
-            attributes::OPENINFERENCE_SPAN_KIND,
-            SpanKind::Reranker.as_str(),
-        );
-        span.set_attribute(attributes::reranker::MODEL_NAME, self.model_name.clone());
+        self.record_into(&span);
+        span
+    }
 
-        if let Some(ref query) = self.query {
-            if !self.config.hide_inputs {
-                span.set_attribute(attributes::reranker::QUERY, query.clone());
-            } else {
-                span.set_attribute(attributes::reranker::QUERY, REDACTED);
-            }
+    /// Record the configured attributes into `sink` instead of a new span.
+    ///
+    /// See [`LlmSpanBuilder::record_into`].
+    pub fn record_into(mut self, sink: &impl AttributeSink) {
+        if let Some(emit) = self.emit_gen_ai {
+            self.config.emit_gen_ai_attributes = emit;
         }
+        set_span_kind(sink, &self.config, self.kind());
+        self.common.record(sink, &self.config);
+
+        if self.config.emit_openinference_attributes {
+            set_value(
+                sink,
+                &self.config,
+                attributes::reranker::MODEL_NAME,
+                self.model_name.clone(),
+            );
 
-        if let Some(top_k) = self.top_k {
-            span.set_attribute(attributes::reranker::TOP_K, top_k);
-        }
+            if let Some(ref query) = self.query {
+                set_redactable(
+                    sink,
+                    &self.config,
+                    attributes::reranker::QUERY,
+                    query.clone(),
+                    self.config.hide_inputs,
+                );
+            }
 
-        for (i, doc) in self.input_documents.iter().enumerate() {
-            if let Some(ref id) = doc.id {
-                span.set_attribute(attributes::reranker::input_documents::id(i), id.clone());
+            if let Some(top_k) = self.top_k {
+                set_value(sink, &self.config, attributes::reranker::TOP_K, top_k);
             }
-            if !self.config.hide_inputs {
-                span.set_attribute(
+
+            for (i, doc) in self.input_documents.iter().enumerate() {
+                if let Some(ref id) = doc.id {
+                    set_value(
+                        sink,
+                        &self.config,
+                        attributes::reranker::input_documents::id(i),
+                        id.clone(),
+                    );
+                }
+                set_redactable(
+                    sink,
+                    &self.config,
                     attributes::reranker::input_documents::content(i),
                     doc.content.clone(),
+                    self.config.hide_inputs,
                 );
-            } else {
-                span.set_attribute(attributes::reranker::input_documents::content(i), REDACTED);
+                if let Some(score) = doc.score {
+                    set_value(
+                        sink,
+                        &self.config,
+                        attributes::reranker::input_documents::score(i),
+                        score,
+                    );
+                }
             }
-            if let Some(score) = doc.score {
-                span.set_attribute(attributes::reranker::input_documents::score(i), score);
+
+            if let Some(tokens) = self.input_tokens {
+                set_value(
+                    sink,
+                    &self.config,
+                    attributes::llm::token_count::PROMPT,
+                    tokens,
+                );
+                set_value(
+                    sink,
+                    &self.config,
+                    attributes::llm::token_count::TOTAL,
+                    tokens,
+                );
             }
         }
 
-        span
+        // -- OTel GenAI attributes --
+        if self.config.emit_gen_ai_attributes {
+            set_value(
+                sink,
+                &self.config,
+                gen_ai::request::MODEL,
+                self.model_name.clone(),
+            );
+            if let Some(tokens) = self.input_tokens {
+                set_value(sink, &self.config, gen_ai::usage::INPUT_TOKENS, tokens);
+            }
+        }
     }
 }
 
 /// Record reranker output documents on a span.
+///
+/// `documents` are expected in reranked order: each one's 1-based position
+/// is recorded as `reranker.output_documents.{i}.document.rank` alongside
+/// its score.
 pub fn record_reranker_output_documents(span: &Span, documents: &[Document], config: &TraceConfig) {
     for (i, doc) in documents.iter().enumerate() {
         if let Some(ref id) = doc.id {
-            span.set_attribute(attributes::reranker::output_documents::id(i), id.clone());
-        }
-        if !config.hide_outputs {
-            span.set_attribute(
-                attributes::reranker::output_documents::content(i),
-                doc.content.clone(),
+            set_value(
+                span,
+                config,
+                attributes::reranker::output_documents::id(i),
+                id.clone(),
             );
-        } else {
-            span.set_attribute(attributes::reranker::output_documents::content(i), REDACTED);
         }
+        set_redactable(
+            span,
+            config,
+            attributes::reranker::output_documents::content(i),
+            doc.content.clone(),
+            config.hide_outputs,
+        );
         if let Some(score) = doc.score {
-            span.set_attribute(attributes::reranker::output_documents::score(i), score);
+            set_value(
+                span,
+                config,
+                attributes::reranker::output_documents::score(i),
+                score,
+            );
+        }
+        set_value(
+            span,
+            config,
+            attributes::reranker::output_documents::rank(i),
+            i as i64 + 1,
+        );
+    }
+}
+
+// =============================================================================
+// Guardrail Span Builder
+// =============================================================================
+
+/// Builder for guardrail spans (input/output safety checks).
+#[derive(Debug)]
+pub struct GuardrailSpanBuilder {
+    name: String,
+    input_value: Option<String>,
+    output_value: Option<String>,
+    policy_name: Option<String>,
+    policy_version: Option<String>,
+    common: CommonAttrs,
+    config: TraceConfig,
+}
+
+impl GuardrailSpanBuilder {
+    /// Create a new guardrail span builder with the given name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            input_value: None,
+            output_value: None,
+            policy_name: None,
+            policy_version: None,
+            common: CommonAttrs::default(),
+            config: TraceConfig::default(),
+        }
+    }
+
+    /// Set the configuration for this builder.
+    pub fn config(mut self, config: TraceConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    common_attr_setters!();
+
+    /// Set the input value.
+    pub fn input_value(mut self, value: impl Into<String>) -> Self {
+        self.input_value = Some(value.into());
+        self
+    }
+
+    /// Set the output value.
+    pub fn output_value(mut self, value: impl Into<String>) -> Self {
+        self.output_value = Some(value.into());
+        self
+    }
+
+    /// Set the name of the policy that evaluated the content.
+    pub fn policy_name(mut self, name: impl Into<String>) -> Self {
+        self.policy_name = Some(name.into());
+        self
+    }
+
+    /// Set the version of the policy that evaluated the content.
+    pub fn policy_version(mut self, version: impl Into<String>) -> Self {
+        self.policy_version = Some(version.into());
+        self
+    }
+
+    /// The OpenInference span kind this builder produces.
+    pub fn kind(&self) -> SpanKind {
+        SpanKind::Guardrail
+    }
+
+    /// Build the span.
+    pub fn build(self) -> Span {
+        let _dropped = DroppedAttributeWarning::new();
+        let span_name = kind_span_name(&self.config, self.kind(), &self.name)
+            .unwrap_or_else(|| format!("guardrail {}", self.name));
+
+        let span = span_at_level!(self.config.span_level, "guardrail", otel.name = %span_name);
+
+        self.record_into(&span);
+        span
+    }
+
+    /// Record the configured attributes into `sink` instead of a new span.
+    ///
+    /// See [`LlmSpanBuilder::record_into`].
+    pub fn record_into(self, sink: &impl AttributeSink) {
+        set_span_kind(sink, &self.config, self.kind());
+        self.common.record(sink, &self.config);
+
+        if self.config.emit_openinference_attributes {
+            if let Some(ref input) = self.input_value {
+                set_redactable(
+                    sink,
+                    &self.config,
+                    attributes::input::VALUE,
+                    input.clone(),
+                    self.config.hide_inputs,
+                );
+            }
+            if let Some(ref output) = self.output_value {
+                set_redactable(
+                    sink,
+                    &self.config,
+                    attributes::output::VALUE,
+                    output.clone(),
+                    self.config.hide_outputs,
+                );
+            }
+
+            if let Some(ref name) = self.policy_name {
+                set_value(
+                    sink,
+                    &self.config,
+                    attributes::guardrail::POLICY_NAME,
+                    name.clone(),
+                );
+            }
+            if let Some(ref version) = self.policy_version {
+                set_value(
+                    sink,
+                    &self.config,
+                    attributes::guardrail::POLICY_VERSION,
+                    version.clone(),
+                );
+            }
+        }
+    }
+}
+
+// =============================================================================
+// Evaluator Span Builder
+// =============================================================================
+
+/// Builder for evaluator spans (model output evaluation).
+#[derive(Debug)]
+pub struct EvaluatorSpanBuilder {
+    name: String,
+    input_value: Option<String>,
+    output_value: Option<String>,
+    common: CommonAttrs,
+    config: TraceConfig,
+}
+
+impl EvaluatorSpanBuilder {
+    /// Create a new evaluator span builder with the given name.
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            input_value: None,
+            output_value: None,
+            common: CommonAttrs::default(),
+            config: TraceConfig::default(),
+        }
+    }
+
+    /// Set the configuration for this builder.
+    pub fn config(mut self, config: TraceConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    common_attr_setters!();
+
+    /// Set the input value.
+    pub fn input_value(mut self, value: impl Into<String>) -> Self {
+        self.input_value = Some(value.into());
+        self
+    }
+
+    /// Set the output value.
+    pub fn output_value(mut self, value: impl Into<String>) -> Self {
+        self.output_value = Some(value.into());
+        self
+    }
+
+    /// The OpenInference span kind this builder produces.
+    pub fn kind(&self) -> SpanKind {
+        SpanKind::Evaluator
+    }
+
+    /// Build the span.
+    pub fn build(self) -> Span {
+        let _dropped = DroppedAttributeWarning::new();
+        let span_name = kind_span_name(&self.config, self.kind(), &self.name)
+            .unwrap_or_else(|| format!("evaluator {}", self.name));
+
+        let span = span_at_level!(self.config.span_level, "evaluator", otel.name = %span_name);
+
+        self.record_into(&span);
+        span
+    }
+
+    /// Record the configured attributes into `sink` instead of a new span.
+    ///
+    /// See [`LlmSpanBuilder::record_into`].
+    pub fn record_into(self, sink: &impl AttributeSink) {
+        set_span_kind(sink, &self.config, self.kind());
+        self.common.record(sink, &self.config);
+
+        if self.config.emit_openinference_attributes {
+            if let Some(ref input) = self.input_value {
+                set_redactable(
+                    sink,
+                    &self.config,
+                    attributes::input::VALUE,
+                    input.clone(),
+                    self.config.hide_inputs,
+                );
+            }
+            if let Some(ref output) = self.output_value {
+                set_redactable(
+                    sink,
+                    &self.config,
+                    attributes::output::VALUE,
+                    output.clone(),
+                    self.config.hide_outputs,
+                );
+            }
+        }
+    }
+}
+
+// =============================================================================
+// Generic Span Builder
+// =============================================================================
+
+/// Builder for span kinds without a dedicated builder.
+///
+/// Records the span kind plus input, output, metadata, and arbitrary
+/// attributes, so new OpenInference kinds can be emitted before a
+/// kind-specific builder exists.
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use openinference_instrumentation::GenericSpanBuilder;
+/// use openinference_semantic_conventions::{attributes, SpanKind};
+///
+/// let span = GenericSpanBuilder::new(SpanKind::Guardrail, "pii-filter")
+///     .input("My SSN is ...")
+///     .attribute(attributes::guardrail::POLICY_NAME, "pii")
+///     .build();
+/// ```
+#[derive(Debug)]
+pub struct GenericSpanBuilder {
+    kind: SpanKind,
+    name: String,
+    input_value: Option<String>,
+    output_value: Option<String>,
+    attributes: Vec<(Key, Value)>,
+    common: CommonAttrs,
+    config: TraceConfig,
+}
+
+impl GenericSpanBuilder {
+    /// Create a new builder for a span of the given kind and name.
+    pub fn new(kind: SpanKind, name: impl Into<String>) -> Self {
+        Self {
+            kind,
+            name: name.into(),
+            input_value: None,
+            output_value: None,
+            attributes: Vec::new(),
+            common: CommonAttrs::default(),
+            config: TraceConfig::default(),
+        }
+    }
+
+    /// Set the configuration for this builder.
+    pub fn config(mut self, config: TraceConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    common_attr_setters!();
+
+    /// Set the input value.
+    pub fn input(mut self, value: impl Into<String>) -> Self {
+        self.input_value = Some(value.into());
+        self
+    }
+
+    /// Set the output value.
+    pub fn output(mut self, value: impl Into<String>) -> Self {
+        self.output_value = Some(value.into());
+        self
+    }
+
+    /// Add an arbitrary attribute (e.g., a kind-specific semantic convention
+    /// key).
+    pub fn attribute(mut self, key: impl Into<Key>, value: impl Into<Value>) -> Self {
+        self.attributes.push((key.into(), value.into()));
+        self
+    }
+
+    /// The OpenInference span kind this builder produces.
+    pub fn kind(&self) -> SpanKind {
+        self.kind
+    }
+
+    /// Build the span.
+    pub fn build(self) -> Span {
+        let _dropped = DroppedAttributeWarning::new();
+        let span_name = kind_span_name(&self.config, self.kind(), &self.name)
+            .unwrap_or_else(|| self.name.clone());
+
+        let span = span_at_level!(self.config.span_level, "openinference", otel.name = %span_name);
+
+        self.record_into(&span);
+        span
+    }
+
+    /// Record the configured attributes into `sink` instead of a new span.
+    ///
+    /// See [`LlmSpanBuilder::record_into`].
+    pub fn record_into(self, sink: &impl AttributeSink) {
+        set_span_kind(sink, &self.config, self.kind());
+        self.common.record(sink, &self.config);
+
+        if self.config.emit_openinference_attributes {
+            if let Some(ref input) = self.input_value {
+                set_redactable(
+                    sink,
+                    &self.config,
+                    attributes::input::VALUE,
+                    input.clone(),
+                    self.config.hide_inputs,
+                );
+            }
+            if let Some(ref output) = self.output_value {
+                set_redactable(
+                    sink,
+                    &self.config,
+                    attributes::output::VALUE,
+                    output.clone(),
+                    self.config.hide_outputs,
+                );
+            }
+
+            for (key, value) in &self.attributes {
+                set_value(sink, &self.config, key.clone(), value.clone());
+            }
+        }
+    }
+}
+
+// =============================================================================
+// Helper functions for recording attributes post-creation
+// =============================================================================
+
+/// Record token usage on a span with the default [`TraceConfig`].
+///
+/// Records both OpenInference (`llm.token_count.*`) and OTel GenAI
+/// (`gen_ai.usage.*`) token count attributes. Use
+/// [`record_token_usage_with_config`] to apply a config.
+pub fn record_token_usage(span: &Span, prompt_tokens: i64, completion_tokens: i64) {
+    record_token_usage_with_config(
+        span,
+        prompt_tokens,
+        completion_tokens,
+        &TraceConfig::default(),
+    );
+}
+
+/// Record token usage on a span.
+///
+/// Records both OpenInference (`llm.token_count.*`) and, when GenAI
+/// attributes are enabled, OTel GenAI (`gen_ai.usage.*`) token count
+/// attributes.
+pub fn record_token_usage_with_config(
+    span: &Span,
+    prompt_tokens: i64,
+    completion_tokens: i64,
+    config: &TraceConfig,
+) {
+    let total_tokens = prompt_tokens + completion_tokens;
+
+    // OpenInference attributes
+    set_value(
+        span,
+        config,
+        attributes::llm::token_count::PROMPT,
+        prompt_tokens,
+    );
+    set_value(
+        span,
+        config,
+        attributes::llm::token_count::COMPLETION,
+        completion_tokens,
+    );
+    set_value(
+        span,
+        config,
+        attributes::llm::token_count::TOTAL,
+        total_tokens,
+    );
+
+    // OTel GenAI attributes
+    if config.emit_gen_ai_attributes {
+        set_value(span, config, gen_ai::usage::INPUT_TOKENS, prompt_tokens);
+        set_value(
+            span,
+            config,
+            gen_ai::usage::OUTPUT_TOKENS,
+            completion_tokens,
+        );
+    }
+}
+
+/// Estimate token usage locally and record it on a span.
+///
+/// For providers that don't report usage. Counts `prompt` and `completion`
+/// with `tokenizer`, records them via [`record_token_usage_with_config`], and sets
+/// `llm.token_count.estimated` to `true`.
+pub fn record_estimated_usage(
+    span: &Span,
+    tokenizer: &impl Tokenizer,
+    prompt: &str,
+    completion: &str,
+    config: &TraceConfig,
+) {
+    let prompt_tokens = tokenizer.count_tokens(prompt) as i64;
+    let completion_tokens = tokenizer.count_tokens(completion) as i64;
+    record_token_usage_with_config(span, prompt_tokens, completion_tokens, config);
+    set_value(span, config, attributes::llm::token_count::ESTIMATED, true);
+}
+
+/// Accumulates token usage across streaming chunks.
+///
+/// Providers report usage incrementally or only in the final chunk; add each
+/// delta as it arrives, then [`flush`](Self::flush) the totals once the stream
+/// ends.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UsageAccumulator {
+    prompt_tokens: i64,
+    completion_tokens: i64,
+}
+
+impl UsageAccumulator {
+    /// Create an empty accumulator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a usage delta from one streaming update.
+    pub fn add_delta(&mut self, prompt_tokens: i64, completion_tokens: i64) {
+        self.prompt_tokens += prompt_tokens;
+        self.completion_tokens += completion_tokens;
+    }
+
+    /// Prompt tokens accumulated so far.
+    pub fn prompt_tokens(&self) -> i64 {
+        self.prompt_tokens
+    }
+
+    /// Completion tokens accumulated so far.
+    pub fn completion_tokens(&self) -> i64 {
+        self.completion_tokens
+    }
+
+    /// Record the accumulated totals on a span via [`record_token_usage`].
+    pub fn flush(&self, span: &Span) {
+        self.flush_with_config(span, &TraceConfig::default());
+    }
+
+    /// Record the accumulated totals on a span via
+    /// [`record_token_usage_with_config`].
+    pub fn flush_with_config(&self, span: &Span, config: &TraceConfig) {
+        record_token_usage_with_config(span, self.prompt_tokens, self.completion_tokens, config);
+    }
+}
+
+/// Record output throughput on a span as `llm.output_tokens_per_second`.
+///
+/// Nothing is recorded for a zero duration.
+pub fn record_throughput(
+    span: &Span,
+    output_tokens: i64,
+    duration: Duration,
+    config: &TraceConfig,
+) {
+    let secs = duration.as_secs_f64();
+    if secs > 0.0 {
+        set_value(
+            span,
+            config,
+            attributes::llm::OUTPUT_TOKENS_PER_SECOND,
+            output_tokens as f64 / secs,
+        );
+    }
+}
+
+/// Record the share of completion tokens spent on reasoning as
+/// `llm.reasoning_ratio`.
+///
+/// Nothing is recorded when `completion_tokens` is zero.
+pub fn record_reasoning_ratio(
+    span: &Span,
+    reasoning_tokens: i64,
+    completion_tokens: i64,
+    config: &TraceConfig,
+) {
+    if completion_tokens > 0 {
+        set_value(
+            span,
+            config,
+            attributes::llm::REASONING_RATIO,
+            reasoning_tokens as f64 / completion_tokens as f64,
+        );
+    }
+}
+
+/// Record embedding input token usage on a span.
+///
+/// Embeddings have no completion, so the input count is recorded as both
+/// `llm.token_count.prompt` and `llm.token_count.total`, plus
+/// `gen_ai.usage.input_tokens` when GenAI attributes are enabled.
+pub fn record_embedding_token_usage(span: &Span, input_tokens: i64, config: &TraceConfig) {
+    set_value(
+        span,
+        config,
+        attributes::llm::token_count::PROMPT,
+        input_tokens,
+    );
+    set_value(
+        span,
+        config,
+        attributes::llm::token_count::TOTAL,
+        input_tokens,
+    );
+    if config.emit_gen_ai_attributes {
+        set_value(span, config, gen_ai::usage::INPUT_TOKENS, input_tokens);
+    }
+}
+
+/// Detailed token breakdown for prompt and completion.
+///
+/// Covers cache usage, reasoning, and per-modality (text/image/audio) counts.
+/// Fields left as `None` are not recorded.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TokenDetails {
+    pub prompt_cache_read: Option<i64>,
+    pub prompt_cache_write: Option<i64>,
+    pub prompt_text: Option<i64>,
+    pub prompt_image: Option<i64>,
+    pub prompt_audio: Option<i64>,
+    pub completion_reasoning: Option<i64>,
+    pub completion_text: Option<i64>,
+    pub completion_image: Option<i64>,
+    pub completion_audio: Option<i64>,
+}
+
+/// Record a detailed token breakdown on a span.
+///
+/// Emits `llm.token_count.prompt_details.*` and
+/// `llm.token_count.completion_details.*` for each populated field.
+pub fn record_token_details(span: &Span, details: &TokenDetails, config: &TraceConfig) {
+    use attributes::llm::token_count::{completion_details, prompt_details};
+
+    let entries = [
+        (prompt_details::CACHE_READ, details.prompt_cache_read),
+        (prompt_details::CACHE_WRITE, details.prompt_cache_write),
+        (prompt_details::TEXT, details.prompt_text),
+        (prompt_details::IMAGE, details.prompt_image),
+        (prompt_details::AUDIO, details.prompt_audio),
+        (completion_details::REASONING, details.completion_reasoning),
+        (completion_details::TEXT, details.completion_text),
+        (completion_details::IMAGE, details.completion_image),
+        (completion_details::AUDIO, details.completion_audio),
+    ];
+    for (key, count) in entries {
+        if let Some(count) = count {
+            set_value(span, config, key, count);
+        }
+    }
+}
+
+/// Prompt-cache cost, split into cache writes and reads.
+///
+/// Providers such as Anthropic bill cache creation and cache hits at
+/// different rates.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CacheCost {
+    /// Cost of writing tokens to the prompt cache.
+    pub write: f64,
+    /// Cost of reading tokens from the prompt cache.
+    pub read: f64,
+}
+
+/// Record prompt-cache cost on a span.
+///
+/// Emits `llm.cost.prompt_details.cache_write` and
+/// `llm.cost.prompt_details.cache_read`.
+pub fn record_cache_cost(span: &Span, cost: &CacheCost, config: &TraceConfig) {
+    use attributes::llm::cost::prompt_details;
+
+    set_value(span, config, prompt_details::CACHE_WRITE, cost.write);
+    set_value(span, config, prompt_details::CACHE_READ, cost.read);
+}
+
+/// Record the token count of the input message at `msg_index`, for APIs
+/// that report usage per message.
+pub fn record_message_tokens(span: &Span, msg_index: usize, tokens: i64, config: &TraceConfig) {
+    set_value(
+        span,
+        config,
+        attributes::llm::input_messages::token_count(msg_index),
+        tokens,
+    );
+}
+
+/// Record citations linking a RAG answer to the documents it cites.
+///
+/// Each citation is `(document_index, document_id)`: the cited document's
+/// position in `retrieval.documents` and its id. Emits
+/// `llm.output.citations.{i}.document_index` and `.document_id`.
+pub fn record_citations(span: &Span, citations: &[(usize, String)], config: &TraceConfig) {
+    use attributes::llm::citations;
+
+    for (i, (document_index, document_id)) in citations.iter().enumerate() {
+        set_value(
+            span,
+            config,
+            citations::document_index(i),
+            *document_index as i64,
+        );
+        set_value(span, config, citations::document_id(i), document_id.clone());
+    }
+}
+
+/// Record an output message on a span at the given index.
+///
+/// Supports arbitrary message indices via dynamic attribute keys.
+/// Content is subject to `TraceConfig` privacy controls, and is emitted as a
+/// `gen_ai.assistant.response` event when `content_as_events` is set.
+pub fn record_output_message(
+    span: &Span,
+    index: usize,
+    role: &str,
+    content: &str,
+    config: &TraceConfig,
+) {
+    let hide_messages = config.should_hide_output_messages();
+    let hide_text = config.should_hide_output_text();
+
+    if config.content_as_events {
+        add_message_event(
+            span,
+            config,
+            gen_ai::events::ASSISTANT_RESPONSE,
+            index,
+            (role, hide_messages),
+            (content, hide_messages || hide_text),
+        );
+        return;
+    }
+
+    set_redactable(
+        span,
+        config,
+        attributes::llm::output_messages::role(index),
+        role.to_string(),
+        hide_messages,
+    );
+    set_redactable(
+        span,
+        config,
+        attributes::llm::output_messages::content(index),
+        content.to_string(),
+        hide_messages || hide_text,
+    );
+}
+
+/// Record the number of output messages on a span.
+///
+/// Emitted as `llm.output_message_count` regardless of privacy settings, so
+/// the count is available even when message content is hidden.
+pub fn record_output_message_count(span: &Span, count: usize, config: &TraceConfig) {
+    set_value(
+        span,
+        config,
+        attributes::llm::OUTPUT_MESSAGE_COUNT,
+        count as i64,
+    );
+}
+
+/// Record multiple completion choices (e.g., for `n > 1`) on a span.
+///
+/// Each `(text, finish_reason)` pair is emitted as
+/// `llm.choices.{i}.completion.text`, subject to `should_hide_choices()`, and
+/// `gen_ai.choice.{i}.finish_reason` when GenAI attributes are enabled.
+pub fn record_choices(span: &Span, choices: &[(String, String)], config: &TraceConfig) {
+    let hide = config.should_hide_choices();
+    for (i, (text, finish_reason)) in choices.iter().enumerate() {
+        set_redactable(
+            span,
+            config,
+            attributes::llm::choices::text(i),
+            text.clone(),
+            hide,
+        );
+        if config.emit_gen_ai_attributes {
+            set_value(
+                span,
+                config,
+                gen_ai::choice::finish_reason(i),
+                finish_reason.clone(),
+            );
+        }
+    }
+}
+
+/// Record an OpenAI-style chat completion response in one call.
+///
+/// `choices` are `(role, content)` pairs recorded as output messages, `usage`
+/// is `(prompt_tokens, completion_tokens)` when reported, and
+/// `finish_reasons` are the raw per-choice finish reasons. `model` is recorded
+/// as `llm.response.model` (and `gen_ai.response.model` with GenAI attributes
+/// enabled). The finish reasons are normalized as in
+/// [`record_finish_reason_with_config`], with the first choice's reason as
+/// `llm.finish_reason` and all of them as `gen_ai.response.finish_reasons`.
+pub fn record_chat_completion(
+    span: &Span,
+    model: &str,
+    usage: Option<(i64, i64)>,
+    choices: &[(String, String)],
+    finish_reasons: &[String],
+    config: &TraceConfig,
+) {
+    for (i, (role, content)) in choices.iter().enumerate() {
+        record_output_message(span, i, role, content, config);
+    }
+    if let Some((prompt_tokens, completion_tokens)) = usage {
+        record_token_usage_with_config(span, prompt_tokens, completion_tokens, config);
+    }
+    set_value(
+        span,
+        config,
+        attributes::llm::response::MODEL,
+        model.to_string(),
+    );
+    if config.emit_gen_ai_attributes {
+        set_value(span, config, gen_ai::response::MODEL, model.to_string());
+    }
+    let raws: Vec<&str> = finish_reasons.iter().map(String::as_str).collect();
+    record_finish_reasons(span, "openai", &raws, config);
+}
+
+/// Record a tool call on an output message with the default [`TraceConfig`].
+///
+/// See [`record_output_tool_call_with_config`].
+pub fn record_output_tool_call(
+    span: &Span,
+    message_index: usize,
+    call_index: usize,
+    tool_call_id: &str,
+    function_name: &str,
+    function_arguments: &str,
+) {
+    record_output_tool_call_with_config(
+        span,
+        message_index,
+        call_index,
+        tool_call_id,
+        function_name,
+        function_arguments,
+        &TraceConfig::default(),
+    );
+}
+
+/// Record a tool call on an output message.
+///
+/// Arguments are redacted when output messages are hidden. With
+/// `config.default_tool_calls_finish_reason`, the first tool call of the
+/// first message also records `tool_calls` as the finish reason, the usual
+/// reason when a model stops to call tools.
+pub fn record_output_tool_call_with_config(
+    span: &Span,
+    message_index: usize,
+    call_index: usize,
+    tool_call_id: &str,
+    function_name: &str,
+    function_arguments: &str,
+    config: &TraceConfig,
+) {
+    set_value(
+        span,
+        config,
+        attributes::llm::output_messages::tool_calls::id(message_index, call_index),
+        tool_call_id.to_string(),
+    );
+    record_tool_call_function(
+        span,
+        message_index,
+        call_index,
+        function_name,
+        function_arguments.to_string(),
+        config,
+    );
+}
+
+/// Record all tool calls of an output message, with their fan-out.
+///
+/// Each `(id, function_name, arguments)` is recorded via
+/// [`record_output_tool_call_with_config`]. The number of calls is recorded as
+/// `llm.parallel_tool_call_count`, and `llm.parallel_tool_calls` is `true`
+/// when there is more than one.
+pub fn record_output_tool_calls(
+    span: &Span,
+    message_index: usize,
+    calls: &[(&str, &str, &str)],
+    config: &TraceConfig,
+) {
+    for (call_index, (id, name, arguments)) in calls.iter().enumerate() {
+        record_output_tool_call_with_config(
+            span,
+            message_index,
+            call_index,
+            id,
+            name,
+            arguments,
+            config,
+        );
+    }
+    set_value(
+        span,
+        config,
+        attributes::llm::PARALLEL_TOOL_CALLS,
+        calls.len() > 1,
+    );
+    set_value(
+        span,
+        config,
+        attributes::llm::PARALLEL_TOOL_CALL_COUNT,
+        calls.len() as i64,
+    );
+}
+
+/// Record a tool call on an output message from structured JSON arguments.
+///
+/// Unlike [`record_output_tool_call_with_config`], the arguments are a
+/// `serde_json::Value` and therefore always serialize to valid JSON. The
+/// function name and arguments are otherwise recorded the same way, including
+/// redaction and the `tool_calls` finish reason default.
+#[cfg(feature = "serde")]
+pub fn record_tool_call_json(
+    span: &Span,
+    message_index: usize,
+    call_index: usize,
+    name: &str,
+    args: serde_json::Value,
+    config: &TraceConfig,
+) {
+    record_tool_call_function(
+        span,
+        message_index,
+        call_index,
+        name,
+        args.to_string(),
+        config,
+    );
+}
+
+/// Record the function name and arguments of an output tool call, shared by
+/// [`record_output_tool_call_with_config`] and `record_tool_call_json`.
+fn record_tool_call_function(
+    span: &Span,
+    message_index: usize,
+    call_index: usize,
+    name: &str,
+    arguments: String,
+    config: &TraceConfig,
+) {
+    use attributes::llm::output_messages::tool_calls;
+
+    default_tool_calls_finish_reason(span, message_index, call_index, config);
+    set_value(
+        span,
+        config,
+        tool_calls::function_name(message_index, call_index),
+        name.to_string(),
+    );
+    set_redactable(
+        span,
+        config,
+        tool_calls::function_arguments(message_index, call_index),
+        arguments,
+        config.should_hide_output_messages(),
+    );
+}
+
+/// Record `tool_calls` as the finish reason on the first tool call of the
+/// first output message, when `config.default_tool_calls_finish_reason` is
+/// set.
+fn default_tool_calls_finish_reason(
+    span: &Span,
+    message_index: usize,
+    call_index: usize,
+    config: &TraceConfig,
+) {
+    if !config.default_tool_calls_finish_reason || message_index != 0 || call_index != 0 {
+        return;
+    }
+    let reason = FinishReason::ToolCalls.as_str().to_string();
+    set_value(span, config, attributes::llm::FINISH_REASON, reason.clone());
+    if config.emit_gen_ai_attributes {
+        set_value(
+            span,
+            config,
+            gen_ai::response::FINISH_REASONS,
+            string_array(&[reason]),
+        );
+    }
+}
+
+/// Record retrieval documents on a span.
+///
+/// At most `config.max_documents_recorded` documents are recorded; when the
+/// list is cut short, `retrieval.documents_truncated` is set to true.
+pub fn record_retrieval_documents(span: &Span, documents: &[Document], config: &TraceConfig) {
+    let limit = config.max_documents_recorded.unwrap_or(usize::MAX);
+    if documents.len() > limit {
+        set_value(
+            span,
+            config,
+            attributes::retrieval::DOCUMENTS_TRUNCATED,
+            true,
+        );
+    }
+    for (i, doc) in documents.iter().take(limit).enumerate() {
+        if let Some(ref id) = doc.id {
+            set_value(
+                span,
+                config,
+                attributes::retrieval::documents::id(i),
+                id.clone(),
+            );
+        }
+        set_redactable(
+            span,
+            config,
+            attributes::retrieval::documents::content(i),
+            doc.content.clone(),
+            config.hide_outputs,
+        );
+        if let Some(score) = doc.score {
+            set_value(
+                span,
+                config,
+                attributes::retrieval::documents::score(i),
+                score,
+            );
+        }
+    }
+}
+
+/// Record an error on a span with the default [`TraceConfig`].
+///
+/// See [`record_error_with_config`].
+pub fn record_error(span: &Span, error_type: &str, message: &str) {
+    record_error_with_config(span, error_type, message, &TraceConfig::default());
+}
+
+/// Record an error on a span.
+pub fn record_error_with_config(
+    span: &Span,
+    error_type: &str,
+    message: &str,
+    config: &TraceConfig,
+) {
+    set_value(
+        span,
+        config,
+        attributes::exception::TYPE,
+        error_type.to_string(),
+    );
+    set_value(
+        span,
+        config,
+        attributes::exception::MESSAGE,
+        message.to_string(),
+    );
+}
+
+/// Standard classification of LLM errors, recorded as `exception.type`.
+///
+/// Using these instead of free-form strings keeps error dashboards consistent
+/// across providers.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum LlmErrorKind {
+    /// The provider rejected the request due to rate limiting.
+    RateLimit,
+    /// The request timed out.
+    Timeout,
+    /// Invalid or missing credentials.
+    Authentication,
+    /// The request was malformed or had invalid parameters.
+    InvalidRequest,
+    /// The prompt exceeded the model's context window.
+    ContextLengthExceeded,
+    /// The request or response was blocked by content filtering.
+    ContentFilter,
+    /// The provider returned a server-side error.
+    ServerError,
+    /// Any other error, with a caller-supplied type name.
+    Other(String),
+}
+
+impl LlmErrorKind {
+    /// Returns the `exception.type` string for this error kind.
+    pub fn as_str(&self) -> &str {
+        match self {
+            LlmErrorKind::RateLimit => "RateLimitError",
+            LlmErrorKind::Timeout => "TimeoutError",
+            LlmErrorKind::Authentication => "AuthenticationError",
+            LlmErrorKind::InvalidRequest => "InvalidRequestError",
+            LlmErrorKind::ContextLengthExceeded => "ContextLengthExceededError",
+            LlmErrorKind::ContentFilter => "ContentFilterError",
+            LlmErrorKind::ServerError => "ServerError",
+            LlmErrorKind::Other(name) => name,
+        }
+    }
+}
+
+impl std::fmt::Display for LlmErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Record a classified error on a span.
+pub fn record_error_kind(span: &Span, kind: LlmErrorKind, message: &str, config: &TraceConfig) {
+    record_error_with_config(span, kind.as_str(), message, config);
+}
+
+/// Record a rate-limit error on a span, with the provider's `Retry-After`
+/// delay as `llm.rate_limit.retry_after` (in seconds).
+pub fn record_rate_limit(span: &Span, retry_after_secs: i64, config: &TraceConfig) {
+    record_error_kind(
+        span,
+        LlmErrorKind::RateLimit,
+        &format!("Rate limited; retry after {retry_after_secs}s"),
+        config,
+    );
+    set_value(
+        span,
+        config,
+        attributes::llm::rate_limit::RETRY_AFTER,
+        retry_after_secs,
+    );
+}
+
+/// Provider-independent reason a generation finished.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum FinishReason {
+    /// The model reached a natural stopping point or a stop sequence.
+    Stop,
+    /// The output hit the max-token limit.
+    Length,
+    /// The model requested one or more tool calls.
+    ToolCalls,
+    /// Output was withheld by content filtering or safety settings.
+    ContentFilter,
+    /// Any other provider-specific reason, kept verbatim.
+    Other(String),
+}
+
+impl FinishReason {
+    /// Normalize a provider's raw finish reason.
+    ///
+    /// Known values from OpenAI (`"stop"`), Anthropic (`"end_turn"`), and
+    /// Gemini (`"STOP"`) map to the same variant; unrecognized values become
+    /// [`FinishReason::Other`].
+    pub fn from_provider(provider: &str, raw: &str) -> Self {
+        let provider = provider.to_ascii_lowercase();
+        let provider_specific = match (provider.as_str(), raw) {
+            ("anthropic", "end_turn" | "stop_sequence" | "pause_turn") => Some(Self::Stop),
+            ("anthropic", "max_tokens") => Some(Self::Length),
+            ("anthropic", "tool_use") => Some(Self::ToolCalls),
+            ("anthropic", "refusal") => Some(Self::ContentFilter),
+            (
+                "google" | "gemini" | "vertex_ai",
+                "SAFETY" | "RECITATION" | "BLOCKLIST" | "PROHIBITED_CONTENT" | "SPII",
+            ) => Some(Self::ContentFilter),
+            _ => None,
+        };
+        if let Some(reason) = provider_specific {
+            return reason;
+        }
+
+        match raw.to_ascii_lowercase().as_str() {
+            "stop" | "end_turn" | "stop_sequence" | "eos" => Self::Stop,
+            "length" | "max_tokens" | "model_length" => Self::Length,
+            "tool_calls" | "function_call" | "tool_use" => Self::ToolCalls,
+            "content_filter" | "safety" => Self::ContentFilter,
+            _ => Self::Other(raw.to_string()),
+        }
+    }
+
+    /// Returns the normalized string recorded as `llm.finish_reason`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            FinishReason::Stop => "stop",
+            FinishReason::Length => "length",
+            FinishReason::ToolCalls => "tool_calls",
+            FinishReason::ContentFilter => "content_filter",
+            FinishReason::Other(raw) => raw,
+        }
+    }
+}
+
+impl std::fmt::Display for FinishReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Record a provider's finish reason on a span with the default
+/// [`TraceConfig`].
+///
+/// See [`record_finish_reason_with_config`].
+pub fn record_finish_reason(span: &Span, provider: &str, raw: &str) -> FinishReason {
+    record_finish_reason_with_config(span, provider, raw, &TraceConfig::default())
+}
+
+/// Record a provider's finish reason on a span.
+///
+/// Emits the normalized form as `llm.finish_reason` (and, when GenAI
+/// attributes are enabled, as a one-element `gen_ai.response.finish_reasons`
+/// array), the raw string as `llm.provider_finish_reason`, plus
+/// `llm.truncated = true` when the response hit the token limit. Returns the
+/// normalized reason.
+pub fn record_finish_reason_with_config(
+    span: &Span,
+    provider: &str,
+    raw: &str,
+    config: &TraceConfig,
+) -> FinishReason {
+    record_finish_reasons(span, provider, &[raw], config)
+        .unwrap_or_else(|| FinishReason::from_provider(provider, raw))
+}
+
+/// Record per-choice finish reasons: the first choice's normalized reason as
+/// `llm.finish_reason` (with its raw form and `llm.truncated`), and all of
+/// them as `gen_ai.response.finish_reasons`. Returns the first reason, or
+/// `None` when `raws` is empty.
+fn record_finish_reasons(
+    span: &Span,
+    provider: &str,
+    raws: &[&str],
+    config: &TraceConfig,
+) -> Option<FinishReason> {
+    let raw = *raws.first()?;
+    let reasons: Vec<String> = raws
+        .iter()
+        .map(|raw| {
+            FinishReason::from_provider(provider, raw)
+                .as_str()
+                .to_string()
+        })
+        .collect();
+    let reason = FinishReason::from_provider(provider, raw);
+    set_value(
+        span,
+        config,
+        attributes::llm::FINISH_REASON,
+        reason.as_str().to_string(),
+    );
+    if config.emit_gen_ai_attributes {
+        set_value(
+            span,
+            config,
+            gen_ai::response::FINISH_REASONS,
+            string_array(&reasons),
+        );
+    }
+    set_value(
+        span,
+        config,
+        attributes::llm::PROVIDER_FINISH_REASON,
+        raw.to_string(),
+    );
+    if reason == FinishReason::Length {
+        set_value(span, config, attributes::llm::TRUNCATED, true);
+    }
+    Some(reason)
+}
+
+/// Record whether the structured output validated against its schema, as
+/// `llm.output_valid`.
+pub fn record_output_valid(span: &Span, valid: bool, config: &TraceConfig) {
+    set_value(span, config, attributes::llm::OUTPUT_VALID, valid);
+}
+
+/// Record the output value on a span.
+pub fn record_output_value(span: &Span, value: &str, config: &TraceConfig) {
+    set_redactable(
+        span,
+        config,
+        attributes::output::VALUE,
+        value.to_string(),
+        config.hide_outputs,
+    );
+}
+
+/// Record the prompt rendered from `llm.prompt_template.template` after
+/// variable substitution, as `llm.prompt_template.rendered`.
+///
+/// Redacted when input text is hidden.
+pub fn record_rendered_prompt(span: &Span, text: &str, config: &TraceConfig) {
+    set_redactable(
+        span,
+        config,
+        attributes::llm::prompt_template::RENDERED,
+        text.to_string(),
+        config.should_hide_input_text(),
+    );
+}
+
+/// Record content filter category scores on a span.
+///
+/// Each `(category, score)` pair is emitted as `llm.content_filter.{category}`.
+pub fn record_content_filter(span: &Span, categories: &[(String, f64)], config: &TraceConfig) {
+    for (category, score) in categories {
+        set_value(
+            span,
+            config,
+            attributes::llm::content_filter::category(category),
+            *score,
+        );
+    }
+}
+
+/// Record per-category safety ratings on a span.
+///
+/// Each `(category, rating)` pair (e.g., `("HARM_CATEGORY_HARASSMENT",
+/// "NEGLIGIBLE")`) is emitted as `llm.safety.{category}`.
+pub fn record_safety_ratings(span: &Span, ratings: &[(String, String)], config: &TraceConfig) {
+    for (category, rating) in ratings {
+        set_value(
+            span,
+            config,
+            attributes::llm::safety::category(category),
+            rating.clone(),
+        );
+    }
+}
+
+/// Record the prompt-construction vs. inference time split on a span.
+///
+/// Emits `llm.prompt_build_ms` and `llm.inference_ms` as whole milliseconds.
+pub fn record_timing_split(
+    span: &Span,
+    prompt_build: Duration,
+    inference: Duration,
+    config: &TraceConfig,
+) {
+    set_value(
+        span,
+        config,
+        attributes::llm::PROMPT_BUILD_MS,
+        duration_millis(prompt_build),
+    );
+    set_value(
+        span,
+        config,
+        attributes::llm::INFERENCE_MS,
+        duration_millis(inference),
+    );
+}
+
+/// Record how long the request waited in the server's queue before
+/// inference, as `gen_ai.server.queue_time` in fractional seconds.
+///
+/// Nothing is recorded when GenAI attributes are disabled.
+pub fn record_queue_time(span: &Span, duration: Duration, config: &TraceConfig) {
+    if config.emit_gen_ai_attributes {
+        set_value(
+            span,
+            config,
+            gen_ai::server::QUEUE_TIME,
+            duration.as_secs_f64(),
+        );
+    }
+}
+
+/// Record how long a tool took to execute, as `tool.duration_ms`.
+pub fn record_tool_duration(span: &Span, duration: Duration, config: &TraceConfig) {
+    set_value(
+        span,
+        config,
+        attributes::tool::DURATION_MS,
+        duration_millis(duration),
+    );
+}
+
+/// Record request and response timestamps (unix milliseconds) on a span.
+///
+/// Useful when the response time is only known after the span is built.
+/// Only the values that are `Some` are recorded.
+pub fn record_timestamps(
+    span: &Span,
+    request: Option<i64>,
+    response: Option<i64>,
+    config: &TraceConfig,
+) {
+    if let Some(ts) = request {
+        set_value(span, config, attributes::llm::request::TIMESTAMP, ts);
+    }
+    if let Some(ts) = response {
+        set_value(span, config, attributes::llm::response::TIMESTAMP, ts);
+    }
+}
+
+/// Record moderation results on a span.
+///
+/// `flagged` is emitted as `llm.flagged`; `scores_json` (a JSON object of
+/// category scores) as `llm.moderation`, redacted when outputs are hidden.
+pub fn record_moderation(span: &Span, flagged: bool, scores_json: &str, config: &TraceConfig) {
+    set_value(span, config, attributes::llm::FLAGGED, flagged);
+    set_redactable(
+        span,
+        config,
+        attributes::llm::MODERATION,
+        scores_json.to_string(),
+        config.hide_outputs,
+    );
+}
+
+/// Record provider response metadata (request ID and rate-limit headers) on a span.
+///
+/// Only the values that are `Some` are recorded.
+pub fn record_provider_metadata(
+    span: &Span,
+    request_id: Option<&str>,
+    rate_limit_remaining: Option<i64>,
+    config: &TraceConfig,
+) {
+    if let Some(request_id) = request_id {
+        set_value(
+            span,
+            config,
+            attributes::llm::PROVIDER_REQUEST_ID,
+            request_id.to_string(),
+        );
+    }
+    if let Some(remaining) = rate_limit_remaining {
+        set_value(
+            span,
+            config,
+            attributes::llm::rate_limit::REMAINING,
+            remaining,
+        );
+    }
+}
+
+/// Record the provider's response object type (e.g., `"chat.completion"`) and
+/// API version on a span.
+///
+/// Only the values that are `Some` are recorded.
+pub fn record_response_object(
+    span: &Span,
+    object: Option<&str>,
+    api_version: Option<&str>,
+    config: &TraceConfig,
+) {
+    if let Some(object) = object {
+        set_value(
+            span,
+            config,
+            attributes::llm::RESPONSE_OBJECT,
+            object.to_string(),
+        );
+    }
+    if let Some(api_version) = api_version {
+        set_value(
+            span,
+            config,
+            attributes::llm::API_VERSION,
+            api_version.to_string(),
+        );
+    }
+}
+
+/// Record the sampling parameters the server reports it actually used.
+///
+/// These may differ from the requested values in `llm.invocation_parameters`
+/// (e.g., when a provider clamps or ignores them), so they are recorded under
+/// separate `llm.response.*` keys. Only the values that are `Some` are
+/// recorded.
+pub fn record_effective_params(
+    span: &Span,
+    temperature: Option<f64>,
+    top_p: Option<f64>,
+    config: &TraceConfig,
+) {
+    if let Some(temperature) = temperature {
+        set_value(
+            span,
+            config,
+            attributes::llm::response::TEMPERATURE,
+            temperature,
+        );
+    }
+    if let Some(top_p) = top_p {
+        set_value(span, config, attributes::llm::response::TOP_P, top_p);
+    }
+}
+
+/// Record an embedding vector on a span at the given index.
+///
+/// The vector is emitted under `embedding.embeddings.{index}.embedding.vector`,
+/// either as a float array or as a base64 string of little-endian `f32` bytes,
+/// depending on `TraceConfig::embedding_vector_encoding`. Vectors are redacted
+/// when `should_hide_embedding_vectors()` is set.
+///
+/// With the `registry` feature, the vector at index 0 also records
+/// `embedding.dimensions` from its length, even when vectors are hidden,
+/// unless the dimensions were already recorded (e.g., via
+/// [`EmbeddingSpanBuilder::dimensions`]). Whether they were is tracked in
+/// `tracing_subscriber::Registry` span extensions, so under other subscribers
+/// nothing is inferred.
+pub fn record_embedding_vector(span: &Span, index: usize, vector: &[f32], config: &TraceConfig) {
+    if index == 0 {
+        record_inferred_dimensions(span, vector.len(), config);
+    }
+    let key = attributes::embedding::embeddings::vector(index);
+    set_value(span, config, key, embedding_vector_value(vector, config));
+}
+
+/// Record a batch of `(text, vector)` embeddings on a span in one pass.
+///
+/// Emits `embedding.embeddings.{i}.embedding.text` and `.vector` for every
+/// item, with the same hide flags and vector encoding as
+/// [`record_embedding_vector`], including `embedding.dimensions` from the
+/// first vector. Keys are built in one reused buffer rather than formatted
+/// per item, and base64 encoding reuses one scratch byte buffer.
+pub fn record_embeddings_batch(span: &Span, items: &[(String, Vec<f32>)], config: &TraceConfig) {
+    use std::fmt::Write as _;
+
+    if let Some((_, first)) = items.first() {
+        record_inferred_dimensions(span, first.len(), config);
+    }
+
+    let mut key = String::from("embedding.embeddings.");
+    let prefix_len = key.len();
+    let mut bytes = Vec::new();
+    for (i, (text, vector)) in items.iter().enumerate() {
+        key.truncate(prefix_len);
+        let _ = write!(key, "{i}.embedding.");
+        let item_len = key.len();
+
+        key.push_str("text");
+        set_redactable(
+            span,
+            config,
+            Key::new(key.clone()),
+            text.clone(),
+            config.hide_embeddings_text,
+        );
+        key.truncate(item_len);
+        key.push_str("vector");
+        set_value(
+            span,
+            config,
+            Key::new(key.clone()),
+            encode_embedding_vector(vector, config, &mut bytes),
+        );
+    }
+}
+
+// =============================================================================
+// Generic GenAI entry point
+// =============================================================================
+
+/// A span builder of whichever kind a `gen_ai.operation.name` maps to.
+#[derive(Debug)]
+pub enum GenAiSpanBuilder {
+    Llm(Box<LlmSpanBuilder>),
+    Embedding(EmbeddingSpanBuilder),
+    Tool(ToolSpanBuilder),
+    Agent(AgentSpanBuilder),
+}
+
+impl GenAiSpanBuilder {
+    /// The OpenInference span kind of the wrapped builder.
+    pub fn kind(&self) -> SpanKind {
+        match self {
+            GenAiSpanBuilder::Llm(b) => b.kind(),
+            GenAiSpanBuilder::Embedding(b) => b.kind(),
+            GenAiSpanBuilder::Tool(b) => b.kind(),
+            GenAiSpanBuilder::Agent(b) => b.kind(),
+        }
+    }
+
+    /// Build the span with the wrapped builder.
+    pub fn build(self) -> Span {
+        match self {
+            GenAiSpanBuilder::Llm(b) => (*b).build(),
+            GenAiSpanBuilder::Embedding(b) => b.build(),
+            GenAiSpanBuilder::Tool(b) => b.build(),
+            GenAiSpanBuilder::Agent(b) => b.build(),
+        }
+    }
+
+    /// Record the wrapped builder's attributes into `sink` instead of a new
+    /// span.
+    pub fn record_into(self, sink: &impl AttributeSink) {
+        match self {
+            GenAiSpanBuilder::Llm(b) => (*b).record_into(sink),
+            GenAiSpanBuilder::Embedding(b) => b.record_into(sink),
+            GenAiSpanBuilder::Tool(b) => b.record_into(sink),
+            GenAiSpanBuilder::Agent(b) => b.record_into(sink),
         }
     }
 }
 
+/// Create a builder for a generic GenAI client call, inferring the span kind
+/// from the `gen_ai.operation.name` value.
+///
+/// `chat`, `text_completion`, and `generate_content` map to LLM spans;
+/// `embeddings` to embedding spans; `execute_tool` to tool spans; and
+/// `create_agent`/`invoke_agent` to agent spans. For tool and agent
+/// operations, `model` is used as the tool or agent name. Returns `None` for
+/// unknown operations.
+pub fn openinference_span_from_gen_ai(operation: &str, model: &str) -> Option<GenAiSpanBuilder> {
+    let builder = match operation {
+        "chat" | "text_completion" | "generate_content" => {
+            GenAiSpanBuilder::Llm(Box::new(LlmSpanBuilder::new(model)))
+        }
+        "embeddings" => GenAiSpanBuilder::Embedding(EmbeddingSpanBuilder::new(model)),
+        "execute_tool" => GenAiSpanBuilder::Tool(ToolSpanBuilder::new(model)),
+        "create_agent" | "invoke_agent" => GenAiSpanBuilder::Agent(AgentSpanBuilder::new(model)),
+        _ => return None,
+    };
+    Some(builder)
+}
+
 // =============================================================================
-// Guardrail Span Builder
+// Decorating existing spans
 // =============================================================================
-
-/// Builder for guardrail spans (input/output safety checks).
-#[derive(Debug)]
-pub struct GuardrailSpanBuilder {
-    name: String,
-    input_value: Option<String>,
-    output_value: Option<String>,
-    config: TraceConfig,
+//
+// For spans created elsewhere (e.g., by a web framework or another
+// instrumentation), these set the span kind and core attributes in place
+// instead of creating a new span.
+
+/// Decorate an existing span as an LLM span with the given model.
+pub fn decorate_llm(span: &Span, model_name: &str, config: &TraceConfig) {
+    decorate_kind(span, config, SpanKind::Llm);
+    if config.emit_openinference_attributes {
+        set_value(
+            span,
+            config,
+            attributes::llm::MODEL_NAME,
+            model_name.to_string(),
+        );
+    }
+    if config.emit_gen_ai_attributes {
+        set_value(span, config, gen_ai::request::MODEL, model_name.to_string());
+    }
 }
 
-impl GuardrailSpanBuilder {
-    /// Create a new guardrail span builder with the given name.
-    pub fn new(name: impl Into<String>) -> Self {
-        Self {
-            name: name.into(),
-            input_value: None,
-            output_value: None,
-            config: TraceConfig::default(),
-        }
+/// Decorate an existing span as an embedding span with the given model.
+pub fn decorate_embedding(span: &Span, model_name: &str, config: &TraceConfig) {
+    decorate_kind(span, config, SpanKind::Embedding);
+    if config.emit_openinference_attributes {
+        set_value(
+            span,
+            config,
+            attributes::embedding::MODEL_NAME,
+            model_name.to_string(),
+        );
     }
+}
 
-    /// Set the configuration for this builder.
-    pub fn config(mut self, config: TraceConfig) -> Self {
-        self.config = config;
-        self
-    }
+/// Decorate an existing span as a chain span.
+pub fn decorate_chain(span: &Span, config: &TraceConfig) {
+    decorate_kind(span, config, SpanKind::Chain);
+}
 
-    /// Set the input value.
-    pub fn input_value(mut self, value: impl Into<String>) -> Self {
-        self.input_value = Some(value.into());
-        self
+/// Decorate an existing span as a tool span with the given tool name.
+pub fn decorate_tool(span: &Span, name: &str, config: &TraceConfig) {
+    decorate_kind(span, config, SpanKind::Tool);
+    if config.emit_openinference_attributes {
+        set_value(span, config, attributes::tool::NAME, name.to_string());
     }
+}
 
-    /// Set the output value.
-    pub fn output_value(mut self, value: impl Into<String>) -> Self {
-        self.output_value = Some(value.into());
-        self
+/// Decorate an existing span as a retriever span.
+pub fn decorate_retriever(span: &Span, config: &TraceConfig) {
+    decorate_kind(span, config, SpanKind::Retriever);
+}
+
+/// Decorate an existing span as an agent span with the given agent name.
+pub fn decorate_agent(span: &Span, name: &str, config: &TraceConfig) {
+    decorate_kind(span, config, SpanKind::Agent);
+    if config.emit_openinference_attributes {
+        set_value(span, config, attributes::agent::NAME, name.to_string());
     }
+}
 
-    /// Build the span.
-    pub fn build(self) -> Span {
-        let span_name = format!("guardrail {}", self.name);
+/// Set the span kind and `openinference.component` shared by all decorators.
+fn decorate_kind(span: &Span, config: &TraceConfig, kind: SpanKind) {
+    set_span_kind(span, config, kind);
+    CommonAttrs::default().record(span, config);
+}
 
-        let span = tracing::info_span!("guardrail", otel.name = %span_name);
+// =============================================================================
+// Recording targets
+// =============================================================================
 
-        span.set_attribute(
-            attributes::OPENINFERENCE_SPAN_KIND,
-            SpanKind::Guardrail.as_str(),
-        );
+/// A target that builders record attributes and events into.
+///
+/// Implemented for `tracing::Span`, where it forwards to
+/// `OpenTelemetrySpanExt`. Every builder has a `record_into` method that
+/// targets a sink directly; see [`LlmSpanBuilder::record_into`].
+pub trait AttributeSink {
+    /// Record an attribute.
+    fn record_attribute(&self, key: Key, value: Value);
+
+    /// Record an event with the given attributes.
+    fn record_event(&self, name: &'static str, attributes: Vec<KeyValue>);
+}
 
-        if let Some(ref input) = self.input_value {
-            if !self.config.hide_inputs {
-                span.set_attribute(attributes::input::VALUE, input.clone());
-            } else {
-                span.set_attribute(attributes::input::VALUE, REDACTED);
-            }
-        }
-        if let Some(ref output) = self.output_value {
-            if !self.config.hide_outputs {
-                span.set_attribute(attributes::output::VALUE, output.clone());
-            } else {
-                span.set_attribute(attributes::output::VALUE, REDACTED);
-            }
+impl AttributeSink for Span {
+    fn record_attribute(&self, key: Key, value: Value) {
+        #[cfg(debug_assertions)]
+        if !self.is_disabled()
+            && !opentelemetry::trace::TraceContextExt::has_active_span(&self.context())
+        {
+            DROPPED_ATTRIBUTES.with(|n| n.set(n.get() + 1));
         }
+        self.set_attribute(key, value);
+    }
 
-        span
+    fn record_event(&self, name: &'static str, attributes: Vec<KeyValue>) {
+        self.add_event(name, attributes);
     }
 }
 
 // =============================================================================
-// Evaluator Span Builder
+// Internal helpers
 // =============================================================================
 
-/// Builder for evaluator spans (model output evaluation).
-#[derive(Debug)]
-pub struct EvaluatorSpanBuilder {
-    name: String,
-    input_value: Option<String>,
-    output_value: Option<String>,
-    config: TraceConfig,
+thread_local! {
+    /// Attributes recorded on enabled spans that had no OpenTelemetry context
+    /// (no `tracing-opentelemetry` layer), counted in debug builds only.
+    static DROPPED_ATTRIBUTES: Cell<usize> = const { Cell::new(0) };
 }
 
-impl EvaluatorSpanBuilder {
-    /// Create a new evaluator span builder with the given name.
-    pub fn new(name: impl Into<String>) -> Self {
+/// Debug-build diagnostic held for the duration of a builder's `build()`.
+///
+/// On drop, emits a `tracing::warn!` with the number of attributes recorded
+/// since creation that `set_attribute` silently discarded because no OTel
+/// layer was active.
+struct DroppedAttributeWarning {
+    start: usize,
+}
+
+impl DroppedAttributeWarning {
+    fn new() -> Self {
         Self {
-            name: name.into(),
-            input_value: None,
-            output_value: None,
-            config: TraceConfig::default(),
+            start: DROPPED_ATTRIBUTES.with(Cell::get),
         }
     }
+}
 
-    /// Set the configuration for this builder.
-    pub fn config(mut self, config: TraceConfig) -> Self {
-        self.config = config;
-        self
-    }
-
-    /// Set the input value.
-    pub fn input_value(mut self, value: impl Into<String>) -> Self {
-        self.input_value = Some(value.into());
-        self
-    }
-
-    /// Set the output value.
-    pub fn output_value(mut self, value: impl Into<String>) -> Self {
-        self.output_value = Some(value.into());
-        self
+impl Drop for DroppedAttributeWarning {
+    fn drop(&mut self) {
+        if !cfg!(debug_assertions) {
+            return;
+        }
+        let dropped = DROPPED_ATTRIBUTES.with(Cell::get).wrapping_sub(self.start);
+        if dropped > 0 {
+            tracing::warn!(
+                dropped,
+                "OpenInference attributes dropped: no OpenTelemetry layer is active"
+            );
+        }
     }
+}
 
-    /// Build the span.
-    pub fn build(self) -> Span {
-        let span_name = format!("evaluator {}", self.name);
+/// Facts about a span that later recorders consult.
+///
+/// OTel attributes can't be read back from a `tracing::Span`, so with the
+/// `registry` feature these live in the span's `tracing_subscriber::Registry`
+/// extensions. Without the feature or a registry nothing is stored.
+#[derive(Debug, Default, Clone, Copy)]
+struct SpanFlags {
+    /// `embedding.dimensions` has been recorded.
+    dimensions: bool,
+}
 
-        let span = tracing::info_span!("evaluator", otel.name = %span_name);
+/// The span's flags, or `None` when they can't be tracked for this span.
+#[cfg(feature = "registry")]
+fn span_flags(span: &Span) -> Option<SpanFlags> {
+    span.with_subscriber(|(id, dispatch)| {
+        let registry = dispatch.downcast_ref::<Registry>()?;
+        let flags = registry.span(id)?.extensions().get::<SpanFlags>().copied();
+        Some(flags.unwrap_or_default())
+    })
+    .flatten()
+}
 
-        span.set_attribute(
-            attributes::OPENINFERENCE_SPAN_KIND,
-            SpanKind::Evaluator.as_str(),
-        );
+#[cfg(not(feature = "registry"))]
+fn span_flags(_span: &Span) -> Option<SpanFlags> {
+    None
+}
 
-        if let Some(ref input) = self.input_value {
-            if !self.config.hide_inputs {
-                span.set_attribute(attributes::input::VALUE, input.clone());
-            } else {
-                span.set_attribute(attributes::input::VALUE, REDACTED);
-            }
-        }
-        if let Some(ref output) = self.output_value {
-            if !self.config.hide_outputs {
-                span.set_attribute(attributes::output::VALUE, output.clone());
-            } else {
-                span.set_attribute(attributes::output::VALUE, REDACTED);
+#[cfg(feature = "registry")]
+fn update_span_flags(span: &Span, update: impl FnOnce(&mut SpanFlags)) {
+    span.with_subscriber(|(id, dispatch)| {
+        let Some(data) = dispatch.downcast_ref::<Registry>().and_then(|r| r.span(id)) else {
+            return;
+        };
+        let mut extensions = data.extensions_mut();
+        match extensions.get_mut::<SpanFlags>() {
+            Some(flags) => update(flags),
+            None => {
+                let mut flags = SpanFlags::default();
+                update(&mut flags);
+                extensions.insert(flags);
             }
         }
+    });
+}
 
-        span
+#[cfg(not(feature = "registry"))]
+fn update_span_flags(_span: &Span, _update: impl FnOnce(&mut SpanFlags)) {}
+
+/// Record `embedding.dimensions` from a vector length when the span is known
+/// not to have it yet. Untracked spans are left alone rather than risk a
+/// second, conflicting value.
+fn record_inferred_dimensions(span: &Span, len: usize, config: &TraceConfig) {
+    if span_flags(span).is_none_or(|flags| flags.dimensions) {
+        return;
     }
+    set_value(span, config, attributes::embedding::DIMENSIONS, len as i64);
+    update_span_flags(span, |flags| flags.dimensions = true);
 }
 
-// =============================================================================
-// Helper functions for recording attributes post-creation
-// =============================================================================
-
-/// Record token usage on a span.
+/// Set an attribute, renaming it per `key_overrides` and `attribute_prefix`
+/// and skipping empty strings when `skip_empty_values` is enabled.
 ///
-/// Records both OpenInference (`llm.token_count.*`) and OTel GenAI
-/// (`gen_ai.usage.*`) token count attributes.
-pub fn record_token_usage(span: &Span, prompt_tokens: i64, completion_tokens: i64) {
-    let total_tokens = prompt_tokens + completion_tokens;
+/// With `emit_openinference_attributes` off, only GenAI keys and the
+/// `openinference.*` span kind, markers, and component are written.
+fn set_value(sink: &impl AttributeSink, config: &TraceConfig, key: Key, value: impl Into<Value>) {
+    if !config.emit_openinference_attributes && !is_gen_ai_key(&key) && !is_marker_key(&key) {
+        return;
+    }
+    let value = value.into();
+    if !is_skipped_empty(config, &value) {
+        sink.record_attribute(remap_key(config, key), value);
+    }
+}
 
-    // OpenInference attributes
-    span.set_attribute(attributes::llm::token_count::PROMPT, prompt_tokens);
-    span.set_attribute(attributes::llm::token_count::COMPLETION, completion_tokens);
-    span.set_attribute(attributes::llm::token_count::TOTAL, total_tokens);
+/// Apply `key_overrides`, or else `attribute_prefix` to OpenInference keys
+/// other than the `openinference.*` markers. Overridden names are used
+/// verbatim.
+fn remap_key(config: &TraceConfig, key: Key) -> Key {
+    if let Some(renamed) = config.key_overrides.get(key.as_str()) {
+        return Key::new(renamed.clone());
+    }
+    match config.attribute_prefix {
+        Some(ref prefix) if !is_gen_ai_key(&key) && !is_marker_key(&key) => {
+            Key::new(format!("{prefix}{}", key.as_str()))
+        }
+        _ => key,
+    }
+}
 
-    // OTel GenAI attributes
-    span.set_attribute(gen_ai::usage::INPUT_TOKENS, prompt_tokens);
-    span.set_attribute(gen_ai::usage::OUTPUT_TOKENS, completion_tokens);
+/// Whether `key` is an `openinference.*` marker (span kind, component,
+/// replay, trace markers), which backends rely on to classify spans.
+fn is_marker_key(key: &Key) -> bool {
+    key.as_str().starts_with("openinference.")
 }
 
-/// Record an output message on a span at the given index.
+/// Whether `key` belongs to the OTel GenAI conventions (including the
+/// general `server.*` keys they reuse) rather than OpenInference.
+fn is_gen_ai_key(key: &Key) -> bool {
+    let key = key.as_str();
+    key.starts_with("gen_ai.") || key.starts_with("server.")
+}
+
+fn is_skipped_empty(config: &TraceConfig, value: &Value) -> bool {
+    config.skip_empty_values && matches!(value, Value::String(s) if s.as_str().is_empty())
+}
+
+/// Set an attribute subject to a privacy flag, replacing it with [`REDACTED`]
+/// when `hide` is true. Empty values are skipped before redaction so that a
+/// redacted span doesn't claim content that was never there.
+fn set_redactable(
+    sink: &impl AttributeSink,
+    config: &TraceConfig,
+    key: Key,
+    value: impl Into<Value>,
+    hide: bool,
+) {
+    let value = value.into();
+    if hide && !is_skipped_empty(config, &value) {
+        set_value(sink, config, key, REDACTED);
+    } else if !hide {
+        set_value(sink, config, key, value);
+    }
+}
+
+/// Add a message as a span event carrying its index, role, and content.
 ///
-/// Supports arbitrary message indices via dynamic attribute keys.
-/// Content is subject to `TraceConfig` privacy controls.
-pub fn record_output_message(
-    span: &Span,
-    index: usize,
-    role: &str,
-    content: &str,
+/// `role` and `content` are paired with their hide flags; hidden fields are
+/// replaced with [`REDACTED`], and empty content is dropped per
+/// `skip_empty_values`.
+fn add_message_event(
+    sink: &impl AttributeSink,
     config: &TraceConfig,
+    name: &'static str,
+    index: usize,
+    (role, hide_role): (&str, bool),
+    (content, hide_content): (&str, bool),
 ) {
-    let hide_messages = config.should_hide_output_messages();
-    let hide_text = config.should_hide_output_text();
+    use gen_ai::events::fields;
 
-    if hide_messages {
-        span.set_attribute(attributes::llm::output_messages::role(index), REDACTED);
-        span.set_attribute(attributes::llm::output_messages::content(index), REDACTED);
-    } else {
-        span.set_attribute(
-            attributes::llm::output_messages::role(index),
-            role.to_string(),
-        );
-        if hide_text {
-            span.set_attribute(attributes::llm::output_messages::content(index), REDACTED);
+    let redact = |value: &str, hide: bool| {
+        if hide {
+            REDACTED.to_string()
         } else {
-            span.set_attribute(
-                attributes::llm::output_messages::content(index),
-                content.to_string(),
-            );
+            value.to_string()
         }
+    };
+    let mut attrs = vec![
+        KeyValue::new(fields::INDEX, index as i64),
+        KeyValue::new(fields::ROLE, redact(role, hide_role)),
+    ];
+    if !(config.skip_empty_values && content.is_empty()) {
+        attrs.push(KeyValue::new(
+            fields::CONTENT,
+            redact(content, hide_content),
+        ));
     }
+    sink.record_event(name, attrs);
 }
 
-/// Record a tool call on an output message.
-pub fn record_output_tool_call(
-    span: &Span,
-    message_index: usize,
-    call_index: usize,
-    tool_call_id: &str,
-    function_name: &str,
-    function_arguments: &str,
-) {
-    span.set_attribute(
-        attributes::llm::output_messages::tool_calls::id(message_index, call_index),
-        tool_call_id.to_string(),
-    );
-    span.set_attribute(
-        attributes::llm::output_messages::tool_calls::function_name(message_index, call_index),
-        function_name.to_string(),
-    );
-    span.set_attribute(
-        attributes::llm::output_messages::tool_calls::function_arguments(message_index, call_index),
-        function_arguments.to_string(),
+/// Set `openinference.span.kind`, lowercased when the config asks for it.
+fn set_span_kind(sink: &impl AttributeSink, config: &TraceConfig, kind: SpanKind) {
+    if config.lowercase_span_kind {
+        set_value(
+            sink,
+            config,
+            attributes::OPENINFERENCE_SPAN_KIND,
+            kind.as_str().to_ascii_lowercase(),
+        );
+    } else {
+        set_value(
+            sink,
+            config,
+            attributes::OPENINFERENCE_SPAN_KIND,
+            kind.as_str(),
+        );
+    }
+}
+
+/// The kind-prefixed span name (e.g., `"LLM: gpt-4"`), if the config asks for one.
+fn kind_span_name(config: &TraceConfig, kind: SpanKind, subject: &str) -> Option<String> {
+    config
+        .include_kind_in_name
+        .then(|| format!("{}: {subject}", kind.as_str()))
+}
+
+/// Debug-assert that a caller-supplied string is valid JSON.
+fn debug_assert_valid_json(json: &str) {
+    debug_assert!(
+        serde_json::from_str::<serde_json::Value>(json).is_ok(),
+        "metadata is not valid JSON: {json}"
     );
 }
 
-/// Record retrieval documents on a span.
-pub fn record_retrieval_documents(span: &Span, documents: &[Document], config: &TraceConfig) {
-    for (i, doc) in documents.iter().enumerate() {
-        if let Some(ref id) = doc.id {
-            span.set_attribute(attributes::retrieval::documents::id(i), id.clone());
-        }
-        if !config.hide_outputs {
-            span.set_attribute(
-                attributes::retrieval::documents::content(i),
-                doc.content.clone(),
-            );
-        } else {
-            span.set_attribute(attributes::retrieval::documents::content(i), REDACTED);
-        }
-        if let Some(score) = doc.score {
-            span.set_attribute(attributes::retrieval::documents::score(i), score);
-        }
-    }
+/// Whole milliseconds in `duration`, saturating at `i64::MAX`.
+fn duration_millis(duration: Duration) -> i64 {
+    i64::try_from(duration.as_millis()).unwrap_or(i64::MAX)
 }
 
-/// Record an error on a span.
-pub fn record_error(span: &Span, error_type: &str, message: &str) {
-    span.set_attribute(attributes::exception::TYPE, error_type.to_string());
-    span.set_attribute(attributes::exception::MESSAGE, message.to_string());
+/// Convert a list of strings into an OTel string-array attribute value.
+fn string_array(values: &[String]) -> Value {
+    Value::Array(Array::String(
+        values.iter().cloned().map(StringValue::from).collect(),
+    ))
 }
 
-/// Record the output value on a span.
-pub fn record_output_value(span: &Span, value: &str, config: &TraceConfig) {
-    if !config.hide_outputs {
-        span.set_attribute(attributes::output::VALUE, value.to_string());
-    } else {
-        span.set_attribute(attributes::output::VALUE, REDACTED);
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// An embedding vector encoded per `embedding_vector_encoding`, or
+/// [`REDACTED`] when vectors are hidden.
+fn embedding_vector_value(vector: &[f32], config: &TraceConfig) -> Value {
+    encode_embedding_vector(vector, config, &mut Vec::new())
+}
+
+/// [`embedding_vector_value`], with `bytes` as reusable scratch space for
+/// base64 encoding.
+fn encode_embedding_vector(vector: &[f32], config: &TraceConfig, bytes: &mut Vec<u8>) -> Value {
+    if config.should_hide_embedding_vectors() {
+        return REDACTED.into();
+    }
+    match config.embedding_vector_encoding {
+        EmbeddingVectorEncoding::Float => {
+            Value::Array(Array::F64(vector.iter().map(|v| f64::from(*v)).collect()))
+        }
+        EmbeddingVectorEncoding::Base64 => encode_f32_base64(vector, bytes).into(),
+    }
+}
+
+/// Encode a vector as standard (padded) base64 over its little-endian `f32`
+/// bytes, which are staged in `bytes`.
+fn encode_f32_base64(vector: &[f32], bytes: &mut Vec<u8>) -> String {
+    bytes.clear();
+    bytes.extend(vector.iter().flat_map(|v| v.to_le_bytes()));
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        out.push(BASE64_ALPHABET[(n >> 18) as usize & 0x3f] as char);
+        out.push(BASE64_ALPHABET[(n >> 12) as usize & 0x3f] as char);
+        out.push(if chunk.len() > 1 {
+            BASE64_ALPHABET[(n >> 6) as usize & 0x3f] as char
+        } else {
+            '='
+        });
+        out.push(if chunk.len() > 2 {
+            BASE64_ALPHABET[n as usize & 0x3f] as char
+        } else {
+            '='
+        });
     }
+    out
 }
 
 #[cfg(test)]
@@ -1067,6 +4080,28 @@ mod tests {
             .try_init();
     }
 
+    #[test]
+    fn test_llm_builder_view() {
+        let builder = LlmSpanBuilder::new_deferred();
+        let view = builder.view();
+        assert_eq!(view.model_name, "");
+        assert_eq!(view.provider, None);
+        assert_eq!(view.temperature, None);
+
+        let builder = builder
+            .model_name("gpt-4")
+            .provider("openai")
+            .temperature(0.7);
+        assert_eq!(
+            builder.view(),
+            LlmSpanBuilderView {
+                model_name: "gpt-4",
+                provider: Some("openai"),
+                temperature: Some(0.7),
+            }
+        );
+    }
+
     #[test]
     fn test_llm_span_builder() {
         init_test_subscriber();
@@ -1276,6 +4311,121 @@ mod tests {
         record_error(&span, "RateLimitError", "Too many requests");
     }
 
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "without a model name")]
+    fn test_default_llm_builder_requires_model() {
+        let _span = LlmSpanBuilder::default().build();
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "metadata is not valid JSON")]
+    fn test_metadata_invalid_json_debug_asserts() {
+        let _builder = ChainSpanBuilder::new("chain").metadata("{not json");
+    }
+
+    #[test]
+    fn test_openinference_span_from_gen_ai() {
+        let chat = openinference_span_from_gen_ai("chat", "gpt-4").unwrap();
+        assert!(matches!(chat, GenAiSpanBuilder::Llm(_)));
+        assert_eq!(chat.kind(), SpanKind::Llm);
+
+        let embeddings =
+            openinference_span_from_gen_ai("embeddings", "text-embedding-3-small").unwrap();
+        assert!(matches!(embeddings, GenAiSpanBuilder::Embedding(_)));
+        assert_eq!(embeddings.kind(), SpanKind::Embedding);
+
+        assert!(openinference_span_from_gen_ai("teleport", "gpt-4").is_none());
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "max_tokens must be non-negative")]
+    fn test_negative_max_tokens_debug_asserts() {
+        let _builder = LlmSpanBuilder::new("gpt-4").max_tokens(-5);
+    }
+
+    #[test]
+    fn test_builder_kinds() {
+        assert_eq!(LlmSpanBuilder::new("gpt-4").kind(), SpanKind::Llm);
+        assert_eq!(EmbeddingSpanBuilder::new("ada").kind(), SpanKind::Embedding);
+        assert_eq!(ChainSpanBuilder::new("chain").kind(), SpanKind::Chain);
+        assert_eq!(ToolSpanBuilder::new("tool").kind(), SpanKind::Tool);
+        assert_eq!(
+            RetrieverSpanBuilder::new("search").kind(),
+            SpanKind::Retriever
+        );
+        assert_eq!(AgentSpanBuilder::new("agent").kind(), SpanKind::Agent);
+        assert_eq!(
+            RerankerSpanBuilder::new("rerank").kind(),
+            SpanKind::Reranker
+        );
+        assert_eq!(
+            GuardrailSpanBuilder::new("guard").kind(),
+            SpanKind::Guardrail
+        );
+        assert_eq!(
+            EvaluatorSpanBuilder::new("eval").kind(),
+            SpanKind::Evaluator
+        );
+    }
+
+    #[test]
+    fn test_finish_reason_normalizes_stop() {
+        assert_eq!(
+            FinishReason::from_provider("openai", "stop"),
+            FinishReason::Stop
+        );
+        assert_eq!(
+            FinishReason::from_provider("anthropic", "end_turn"),
+            FinishReason::Stop
+        );
+        assert_eq!(
+            FinishReason::from_provider("google", "STOP"),
+            FinishReason::Stop
+        );
+    }
+
+    #[test]
+    fn test_finish_reason_other_kinds() {
+        assert_eq!(
+            FinishReason::from_provider("anthropic", "max_tokens"),
+            FinishReason::Length
+        );
+        assert_eq!(
+            FinishReason::from_provider("openai", "tool_calls"),
+            FinishReason::ToolCalls
+        );
+        assert_eq!(
+            FinishReason::from_provider("gemini", "SAFETY"),
+            FinishReason::ContentFilter
+        );
+        assert_eq!(
+            FinishReason::from_provider("acme", "weird"),
+            FinishReason::Other("weird".to_string())
+        );
+    }
+
+    #[test]
+    fn test_llm_error_kind_as_str() {
+        assert_eq!(LlmErrorKind::RateLimit.as_str(), "RateLimitError");
+        assert_eq!(LlmErrorKind::Timeout.as_str(), "TimeoutError");
+        assert_eq!(LlmErrorKind::Authentication.as_str(), "AuthenticationError");
+        assert_eq!(LlmErrorKind::InvalidRequest.as_str(), "InvalidRequestError");
+        assert_eq!(
+            LlmErrorKind::ContextLengthExceeded.as_str(),
+            "ContextLengthExceededError"
+        );
+        assert_eq!(LlmErrorKind::ContentFilter.as_str(), "ContentFilterError");
+        assert_eq!(LlmErrorKind::ServerError.as_str(), "ServerError");
+        assert_eq!(
+            LlmErrorKind::Other("QuotaExceeded".to_string()).as_str(),
+            "QuotaExceeded"
+        );
+        assert_eq!(format!("{}", LlmErrorKind::Timeout), "TimeoutError");
+    }
+
     #[test]
     fn test_record_retrieval_documents() {
         init_test_subscriber();
@@ -1312,6 +4462,43 @@ mod tests {
         record_output_value(&span, "secret result", &hidden_config);
     }
 
+    /// Decode the output of `encode_f32_base64` back into floats.
+    fn decode_f32_base64(encoded: &str) -> Vec<f32> {
+        let mut bytes = Vec::new();
+        for chunk in encoded.as_bytes().chunks(4) {
+            let mut n = 0u32;
+            let mut len = 0;
+            for &c in chunk {
+                n <<= 6;
+                if c != b'=' {
+                    n |= BASE64_ALPHABET.iter().position(|&a| a == c).unwrap() as u32;
+                    len += 1;
+                }
+            }
+            let decoded = n.to_be_bytes();
+            bytes.extend_from_slice(&decoded[1..len]);
+        }
+        bytes
+            .chunks_exact(4)
+            .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+            .collect()
+    }
+
+    #[test]
+    fn test_encode_f32_base64_round_trip() {
+        for vector in [
+            vec![],
+            vec![1.0],
+            vec![1.0, 2.0],
+            vec![0.5, -0.25, 3.75],
+            vec![0.1, 0.2, 0.3, 0.4, 0.5],
+        ] {
+            let encoded = encode_f32_base64(&vector, &mut Vec::new());
+            assert_eq!(encoded.len() % 4, 0);
+            assert_eq!(decode_f32_base64(&encoded), vector);
+        }
+    }
+
     #[test]
     fn test_trace_config_default() {
         let config = TraceConfig::default();
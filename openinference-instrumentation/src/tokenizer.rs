@@ -0,0 +1,12 @@
+//! Pluggable local token counting.
+//!
+//! Some providers don't report token usage. Implement [`Tokenizer`] over the
+//! model's tokenizer (e.g., `tiktoken-rs`, `tokenizers`) and pass it to
+//! [`record_estimated_usage`](crate::span_builder::record_estimated_usage)
+//! to record estimated counts.
+
+/// Counts tokens in text for a particular model.
+pub trait Tokenizer {
+    /// Number of tokens `text` encodes to.
+    fn count_tokens(&self, text: &str) -> usize;
+}
@@ -2,16 +2,18 @@
 //! and verify that exported spans have the correct OpenInference and GenAI attributes.
 
 use opentelemetry::trace::TracerProvider as _;
-use opentelemetry::Value;
+use opentelemetry::{Array, Value};
 use opentelemetry_sdk::trace::{InMemorySpanExporterBuilder, SdkTracerProvider, SpanData};
 use tracing_opentelemetry::OpenTelemetryLayer;
 use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::Registry;
 
 use openinference_instrumentation::span_builder::{
-    ChainSpanBuilder, EmbeddingSpanBuilder, LlmSpanBuilder, RetrieverSpanBuilder, ToolSpanBuilder,
+    AgentSpanBuilder, ChainSpanBuilder, Document, EmbeddingSpanBuilder, GenericSpanBuilder,
+    GuardrailSpanBuilder, LlmSpanBuilder, RerankerSpanBuilder, RetrieverSpanBuilder,
+    ToolSpanBuilder,
 };
-use openinference_instrumentation::TraceConfig;
+use openinference_instrumentation::{EmbeddingVectorEncoding, TraceConfig};
 
 // =============================================================================
 // Test harness
@@ -112,6 +114,51 @@ fn assert_f64_attribute(span: &SpanData, key: &str, expected: f64) {
     }
 }
 
+/// Assert that a span contains an attribute with the given bool value.
+fn assert_bool_attribute(span: &SpanData, key: &str, expected: bool) {
+    let val = find_attribute(span, key).unwrap_or_else(|| {
+        panic!(
+            "attribute '{}' not found in span. attributes: {:?}",
+            key, span.attributes
+        )
+    });
+    match &val {
+        Value::Bool(v) => assert_eq!(
+            *v, expected,
+            "attribute '{}' expected {}, got {}",
+            key, expected, v
+        ),
+        other => panic!(
+            "attribute '{}' expected Bool({}), got {:?}",
+            key, expected, other
+        ),
+    }
+}
+
+/// Assert that a span contains a string-array attribute with the given elements.
+fn assert_string_array_attribute(span: &SpanData, key: &str, expected: &[&str]) {
+    let val = find_attribute(span, key).unwrap_or_else(|| {
+        panic!(
+            "attribute '{}' not found in span. attributes: {:?}",
+            key, span.attributes
+        )
+    });
+    match &val {
+        Value::Array(Array::String(values)) => {
+            let actual: Vec<&str> = values.iter().map(|v| v.as_str()).collect();
+            assert_eq!(
+                actual, expected,
+                "attribute '{}' expected {:?}, got {:?}",
+                key, expected, actual
+            );
+        }
+        other => panic!(
+            "attribute '{}' expected Array(String({:?})), got {:?}",
+            key, expected, other
+        ),
+    }
+}
+
 /// Assert that a span does NOT contain an attribute with the given key.
 fn assert_no_attribute(span: &SpanData, key: &str) {
     if let Some(val) = find_attribute(span, key) {
@@ -208,7 +255,7 @@ fn test_token_usage_recording() {
 
     tracing::subscriber::with_default(subscriber, || {
         // The current LlmSpanBuilder does NOT declare token count fields in the
-        // span!() macro, so record_token_usage() calls span.record() on
+        // span!() macro, so record_token_usage_with_config() calls span.record() on
         // undeclared fields -- which is silently ignored by tracing.
         let span = LlmSpanBuilder::new("gpt-4").build();
         openinference_instrumentation::span_builder::record_token_usage(&span, 100, 50);
@@ -775,3 +822,3237 @@ fn test_embedding_text_hidden() {
         "__REDACTED__",
     );
 }
+
+// =============================================================================
+// Content filter test
+// =============================================================================
+
+#[test]
+fn test_record_content_filter() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = LlmSpanBuilder::new("gpt-4").build();
+        openinference_instrumentation::span_builder::record_content_filter(
+            &span,
+            &[("hate".to_string(), 0.02), ("violence".to_string(), 0.91)],
+            &TraceConfig::default(),
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_f64_attribute(span, "llm.content_filter.hate", 0.02);
+    assert_f64_attribute(span, "llm.content_filter.violence", 0.91);
+}
+
+// =============================================================================
+// Embedding encoding formats test
+// =============================================================================
+
+#[test]
+fn test_embedding_encoding_formats() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = EmbeddingSpanBuilder::new("text-embedding-3-small")
+            .encoding_format("float")
+            .encoding_format("base64")
+            .build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_string_array_attribute(span, "embedding.encoding_formats", &["float", "base64"]);
+    assert_string_array_attribute(
+        span,
+        "gen_ai.request.encoding_formats",
+        &["float", "base64"],
+    );
+}
+
+// =============================================================================
+// Replay marker test
+// =============================================================================
+
+#[test]
+fn test_replay_marker() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = LlmSpanBuilder::new("gpt-4").replay(true).build();
+        drop(span);
+        let span = ChainSpanBuilder::new("live_chain").build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 2);
+
+    assert_bool_attribute(&spans[0], "openinference.replay", true);
+    assert_no_attribute(&spans[1], "openinference.replay");
+}
+
+// =============================================================================
+// build_with test
+// =============================================================================
+
+#[test]
+fn test_llm_build_with_records_post_build_attributes() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = LlmSpanBuilder::new("gpt-4").build_with(|span| {
+            openinference_instrumentation::span_builder::record_token_usage(span, 100, 50);
+        });
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_string_attribute(span, "llm.model_name", "gpt-4");
+    assert_i64_attribute(span, "llm.token_count.prompt", 100);
+    assert_i64_attribute(span, "llm.token_count.completion", 50);
+    assert_i64_attribute(span, "llm.token_count.total", 150);
+}
+
+// =============================================================================
+// Provider metadata test
+// =============================================================================
+
+#[test]
+fn test_record_provider_metadata() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = LlmSpanBuilder::new("gpt-4").build();
+        openinference_instrumentation::span_builder::record_provider_metadata(
+            &span,
+            Some("req_abc123"),
+            Some(42),
+            &TraceConfig::default(),
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_string_attribute(span, "llm.provider_request_id", "req_abc123");
+    assert_i64_attribute(span, "llm.rate_limit.remaining", 42);
+}
+
+// =============================================================================
+// Server address/port test
+// =============================================================================
+
+#[test]
+fn test_llm_server_address_and_port() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = LlmSpanBuilder::new("llama3")
+            .provider("ollama")
+            .server_address("localhost")
+            .server_port(11434)
+            .build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_string_attribute(span, "server.address", "localhost");
+    assert_i64_attribute(span, "server.port", 11434);
+}
+
+// =============================================================================
+// Embedding vector encoding tests
+// =============================================================================
+
+#[test]
+fn test_record_embedding_vector_base64() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    let config = TraceConfig::builder()
+        .embedding_vector_encoding(EmbeddingVectorEncoding::Base64)
+        .build();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = EmbeddingSpanBuilder::new("ada-002").build();
+        openinference_instrumentation::span_builder::record_embedding_vector(
+            &span,
+            0,
+            &[0.5, -0.25, 3.75],
+            &config,
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    // Little-endian f32 bytes of [0.5, -0.25, 3.75], base64-encoded
+    assert_string_attribute(
+        span,
+        "embedding.embeddings.0.embedding.vector",
+        "AAAAPwAAgL4AAHBA",
+    );
+}
+
+#[test]
+fn test_record_embedding_vector_float() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    let config = TraceConfig::default();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = EmbeddingSpanBuilder::new("ada-002").build();
+        openinference_instrumentation::span_builder::record_embedding_vector(
+            &span,
+            0,
+            &[0.5, -0.25],
+            &config,
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+
+    match find_attribute(&spans[0], "embedding.embeddings.0.embedding.vector") {
+        Some(Value::Array(Array::F64(values))) => assert_eq!(values, vec![0.5, -0.25]),
+        other => panic!("expected F64 array, got {:?}", other),
+    }
+}
+
+// =============================================================================
+// Deferred model name test
+// =============================================================================
+
+#[test]
+fn test_llm_deferred_model_name() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let builder = LlmSpanBuilder::new_deferred().provider("openai");
+        // ... model selected by a router ...
+        let span = builder.model_name("gpt-4o").build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_eq!(span.name, "llm gpt-4o");
+    assert_string_attribute(span, "llm.model_name", "gpt-4o");
+    assert_string_attribute(span, "gen_ai.request.model", "gpt-4o");
+}
+
+// =============================================================================
+// Error classification test
+// =============================================================================
+
+#[test]
+fn test_record_error_kind() {
+    use openinference_instrumentation::LlmErrorKind;
+
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        for kind in [
+            LlmErrorKind::RateLimit,
+            LlmErrorKind::ContextLengthExceeded,
+            LlmErrorKind::Other("QuotaExceeded".to_string()),
+        ] {
+            let span = LlmSpanBuilder::new("gpt-4").build();
+            openinference_instrumentation::span_builder::record_error_kind(
+                &span,
+                kind,
+                "request failed",
+                &TraceConfig::default(),
+            );
+            drop(span);
+        }
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 3);
+
+    assert_string_attribute(&spans[0], "exception.type", "RateLimitError");
+    assert_string_attribute(&spans[1], "exception.type", "ContextLengthExceededError");
+    assert_string_attribute(&spans[2], "exception.type", "QuotaExceeded");
+    assert_string_attribute(&spans[2], "exception.message", "request failed");
+}
+
+// =============================================================================
+// Tool count / tool choice test
+// =============================================================================
+
+#[test]
+fn test_llm_tool_count_and_choice() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = LlmSpanBuilder::new("gpt-4")
+            .tool(r#"{"name":"get_weather"}"#)
+            .tool(r#"{"name":"search"}"#)
+            .tool_choice("required")
+            .invocation_parameters(r#"{"temperature":0.2}"#)
+            .build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_i64_attribute(span, "llm.tool_count", 2);
+    assert_string_attribute(span, "llm.tool_choice", "required");
+    assert_string_attribute(
+        span,
+        "llm.invocation_parameters",
+        r#"{"temperature":0.2,"tool_choice":"required"}"#,
+    );
+}
+
+// =============================================================================
+// Typed chain input test
+// =============================================================================
+
+#[test]
+fn test_chain_typed_input_value() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = ChainSpanBuilder::new("add_one").input_value(41i64).build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_i64_attribute(span, "input.value", 41);
+    assert_string_attribute(span, "input.mime_type", "application/json");
+}
+
+// =============================================================================
+// Empty value skipping tests
+// =============================================================================
+
+#[test]
+fn test_skip_empty_values_omits_empty_content() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = LlmSpanBuilder::new("gpt-4")
+            .input_message("user", "")
+            .build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_string_attribute(span, "llm.input_messages.0.message.role", "user");
+    assert_no_attribute(span, "llm.input_messages.0.message.content");
+}
+
+#[test]
+fn test_skip_empty_values_disabled_records_empty_content() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let config = TraceConfig::builder().skip_empty_values(false).build();
+        let span = LlmSpanBuilder::new("gpt-4")
+            .config(config)
+            .input_message("user", "")
+            .build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_string_attribute(span, "llm.input_messages.0.message.content", "");
+}
+
+// =============================================================================
+// Per-modality token details test
+// =============================================================================
+
+#[test]
+fn test_token_details_image_prompt_tokens() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = LlmSpanBuilder::new("gpt-4o").build();
+        openinference_instrumentation::span_builder::record_token_details(
+            &span,
+            &openinference_instrumentation::span_builder::TokenDetails {
+                prompt_text: Some(120),
+                prompt_image: Some(765),
+                ..Default::default()
+            },
+            &TraceConfig::default(),
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_i64_attribute(span, "llm.token_count.prompt_details.image", 765);
+    assert_i64_attribute(span, "llm.token_count.prompt_details.text", 120);
+    assert_no_attribute(span, "llm.token_count.prompt_details.audio");
+}
+
+// =============================================================================
+// Trace root / final marker tests
+// =============================================================================
+
+#[test]
+fn test_trace_root_and_final_markers() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let root = ChainSpanBuilder::new("pipeline").trace_root(true).build();
+        let _guard = root.enter();
+        let last = LlmSpanBuilder::new("gpt-4").trace_final(true).build();
+        drop(last);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 2);
+    let root = spans.iter().find(|s| s.name == "pipeline").unwrap();
+    let last = spans.iter().find(|s| s.name != "pipeline").unwrap();
+
+    assert_bool_attribute(root, "openinference.trace.root", true);
+    assert_no_attribute(root, "openinference.trace.final");
+    assert_bool_attribute(last, "openinference.trace.final", true);
+    assert_no_attribute(last, "openinference.trace.root");
+}
+
+// =============================================================================
+// Throughput test
+// =============================================================================
+
+#[test]
+fn test_record_throughput() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = LlmSpanBuilder::new("gpt-4").build();
+        openinference_instrumentation::span_builder::record_throughput(
+            &span,
+            300,
+            std::time::Duration::from_millis(1500),
+            &TraceConfig::default(),
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_f64_attribute(span, "llm.output_tokens_per_second", 200.0);
+}
+
+// =============================================================================
+// GenAI-only emission test
+// =============================================================================
+
+#[test]
+fn test_emit_only_gen_ai_attributes() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let config = TraceConfig::builder()
+            .emit_openinference_attributes(false)
+            .build();
+        let span = LlmSpanBuilder::new("gpt-4")
+            .config(config.clone())
+            .provider("openai")
+            .temperature(0.5)
+            .input_message("user", "Hello")
+            .build();
+        openinference_instrumentation::span_builder::record_token_usage_with_config(
+            &span, 10, 5, &config,
+        );
+        openinference_instrumentation::span_builder::record_finish_reason_with_config(
+            &span, "openai", "stop", &config,
+        );
+        openinference_instrumentation::span_builder::record_error_with_config(
+            &span,
+            "TimeoutError",
+            "timed out",
+            &config,
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_string_attribute(span, "openinference.span.kind", "LLM");
+    assert_string_attribute(span, "gen_ai.request.model", "gpt-4");
+    assert_string_attribute(span, "gen_ai.provider.name", "openai");
+    assert_f64_attribute(span, "gen_ai.request.temperature", 0.5);
+    assert_i64_attribute(span, "gen_ai.usage.input_tokens", 10);
+    assert_string_array_attribute(span, "gen_ai.response.finish_reasons", &["stop"]);
+
+    assert_no_attribute(span, "llm.model_name");
+    assert_no_attribute(span, "llm.provider");
+    assert_no_attribute(span, "llm.input_messages.0.message.content");
+    assert_no_attribute(span, "llm.token_count.prompt");
+    assert_no_attribute(span, "llm.finish_reason");
+    assert_no_attribute(span, "exception.type");
+}
+
+// =============================================================================
+// llm_span! macro test
+// =============================================================================
+
+#[test]
+fn test_llm_span_macro() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = openinference_instrumentation::llm_span! {
+            model: "gpt-4",
+            provider: "openai",
+            temperature: 0.7,
+        };
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_string_attribute(span, "openinference.span.kind", "LLM");
+    assert_string_attribute(span, "llm.model_name", "gpt-4");
+    assert_string_attribute(span, "llm.provider", "openai");
+    assert_f64_attribute(span, "gen_ai.request.temperature", 0.7);
+}
+
+// =============================================================================
+// Moderation tests
+// =============================================================================
+
+#[test]
+fn test_record_moderation() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = LlmSpanBuilder::new("gpt-4").build();
+        openinference_instrumentation::span_builder::record_moderation(
+            &span,
+            true,
+            r#"{"hate":0.01,"violence":0.92}"#,
+            &TraceConfig::default(),
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_bool_attribute(span, "llm.flagged", true);
+    assert_string_attribute(span, "llm.moderation", r#"{"hate":0.01,"violence":0.92}"#);
+}
+
+// =============================================================================
+// Finish reason test
+// =============================================================================
+
+#[test]
+fn test_record_finish_reason_raw_and_normalized() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = LlmSpanBuilder::new("claude-3").build();
+        openinference_instrumentation::span_builder::record_finish_reason(
+            &span,
+            "anthropic",
+            "end_turn",
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_string_attribute(span, "llm.finish_reason", "stop");
+    assert_string_array_attribute(span, "gen_ai.response.finish_reasons", &["stop"]);
+    assert_string_attribute(span, "llm.provider_finish_reason", "end_turn");
+}
+
+// =============================================================================
+// Content-as-events tests
+// =============================================================================
+
+#[test]
+fn test_content_as_events() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let config = TraceConfig::builder().content_as_events(true).build();
+        let span = LlmSpanBuilder::new("gpt-4")
+            .config(config.clone())
+            .input_message("system", "Be brief.")
+            .input_message("user", "Hello")
+            .build();
+        openinference_instrumentation::span_builder::record_output_message(
+            &span,
+            0,
+            "assistant",
+            "Hi!",
+            &config,
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_no_attribute(span, "llm.input_messages.0.message.content");
+    assert_no_attribute(span, "llm.input_messages.1.message.content");
+    assert_no_attribute(span, "llm.output_messages.0.message.content");
+
+    let names: Vec<&str> = span.events.iter().map(|e| e.name.as_ref()).collect();
+    assert_eq!(
+        names,
+        [
+            "gen_ai.system.prompt",
+            "gen_ai.user.prompt",
+            "gen_ai.assistant.response"
+        ]
+    );
+    let response = &span.events.events[2];
+    let content = response
+        .attributes
+        .iter()
+        .find(|kv| kv.key.as_str() == "content")
+        .expect("content attribute on event");
+    assert_eq!(content.value.as_str(), "Hi!");
+}
+
+#[test]
+fn test_content_as_events_maps_history_roles() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let config = TraceConfig::builder().content_as_events(true).build();
+        let span = LlmSpanBuilder::new("gpt-4")
+            .config(config)
+            .input_message("user", "What's 2+2?")
+            .input_message("assistant", "4")
+            .input_message("tool", "{\"result\": 4}")
+            .input_message("user", "Thanks")
+            .build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+
+    let names: Vec<&str> = spans[0].events.iter().map(|e| e.name.as_ref()).collect();
+    assert_eq!(
+        names,
+        [
+            "gen_ai.user.prompt",
+            "gen_ai.assistant.response",
+            "gen_ai.tool.message",
+            "gen_ai.user.prompt"
+        ]
+    );
+}
+
+// =============================================================================
+// Default LLM builder test
+// =============================================================================
+
+#[test]
+fn test_llm_builder_default_then_model() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = LlmSpanBuilder::default()
+            .provider("anthropic")
+            .model_name("claude-3")
+            .build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_eq!(span.name, "llm claude-3");
+    assert_string_attribute(span, "llm.model_name", "claude-3");
+    assert_string_attribute(span, "llm.provider", "anthropic");
+}
+
+// =============================================================================
+// Timestamp tests
+// =============================================================================
+
+#[test]
+fn test_request_and_response_timestamps() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = LlmSpanBuilder::new("gpt-4")
+            .request_timestamp(1_700_000_000_000)
+            .build();
+        openinference_instrumentation::span_builder::record_timestamps(
+            &span,
+            None,
+            Some(1_700_000_001_250),
+            &TraceConfig::default(),
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_i64_attribute(span, "llm.request.timestamp", 1_700_000_000_000);
+    assert_i64_attribute(span, "llm.response.timestamp", 1_700_000_001_250);
+}
+
+// =============================================================================
+// Agent state privacy tests
+// =============================================================================
+
+fn export_agent_with_state(config: TraceConfig) -> SpanData {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = AgentSpanBuilder::new("planner")
+            .config(config)
+            .goal("Book a flight")
+            .plan("1. search 2. compare 3. book")
+            .state(r#"{"step":2}"#)
+            .build();
+        drop(span);
+    });
+
+    let mut spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    spans.remove(0)
+}
+
+#[test]
+fn test_agent_state_visible_by_default() {
+    let span = export_agent_with_state(TraceConfig::default());
+
+    assert_string_attribute(&span, "agent.goal", "Book a flight");
+    assert_string_attribute(&span, "agent.plan", "1. search 2. compare 3. book");
+    assert_string_attribute(&span, "agent.state", r#"{"step":2}"#);
+}
+
+#[test]
+fn test_hide_agent_state_redacts() {
+    let config = TraceConfig::builder().hide_agent_state(true).build();
+    let span = export_agent_with_state(config);
+
+    assert_string_attribute(&span, "agent.goal", "__REDACTED__");
+    assert_string_attribute(&span, "agent.plan", "__REDACTED__");
+    assert_string_attribute(&span, "agent.state", "__REDACTED__");
+}
+
+#[test]
+fn test_hide_outputs_redacts_agent_state() {
+    let config = TraceConfig::builder().hide_outputs(true).build();
+    let span = export_agent_with_state(config);
+
+    assert_string_attribute(&span, "agent.goal", "__REDACTED__");
+    assert_string_attribute(&span, "agent.plan", "__REDACTED__");
+    assert_string_attribute(&span, "agent.state", "__REDACTED__");
+}
+
+// =============================================================================
+// Decorating existing spans
+// =============================================================================
+
+#[test]
+fn test_decorate_existing_span_as_llm() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = tracing::info_span!("framework_request");
+        openinference_instrumentation::span_builder::decorate_llm(
+            &span,
+            "gpt-4",
+            &TraceConfig::default(),
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_eq!(span.name, "framework_request");
+    assert_string_attribute(span, "openinference.span.kind", "LLM");
+    assert_string_attribute(span, "llm.model_name", "gpt-4");
+    assert_string_attribute(span, "gen_ai.request.model", "gpt-4");
+}
+
+#[test]
+fn test_decorate_chain_and_retriever_respect_config() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let config = TraceConfig::builder()
+            .lowercase_span_kind(true)
+            .component_name("ingest")
+            .build();
+        let span = tracing::info_span!("pipeline");
+        openinference_instrumentation::span_builder::decorate_chain(&span, &config);
+        drop(span);
+
+        let span = tracing::info_span!("lookup");
+        openinference_instrumentation::span_builder::decorate_retriever(&span, &config);
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 2);
+
+    assert_string_attribute(&spans[0], "openinference.span.kind", "chain");
+    assert_string_attribute(&spans[0], "openinference.component", "ingest");
+    assert_string_attribute(&spans[1], "openinference.span.kind", "retriever");
+    assert_string_attribute(&spans[1], "openinference.component", "ingest");
+}
+
+// =============================================================================
+// System prompt test
+// =============================================================================
+
+#[test]
+fn test_system_prompt_is_first_message() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = LlmSpanBuilder::new("gpt-4")
+            .input_message("user", "Hello")
+            .system_prompt("You are terse.")
+            .build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_string_attribute(span, "llm.input_messages.0.message.role", "system");
+    assert_string_attribute(
+        span,
+        "llm.input_messages.0.message.content",
+        "You are terse.",
+    );
+    assert_string_attribute(span, "llm.input_messages.1.message.role", "user");
+    assert_string_attribute(span, "llm.input_messages.1.message.content", "Hello");
+    assert_string_attribute(span, "gen_ai.system_instructions", "You are terse.");
+}
+
+// =============================================================================
+// Timing split test
+// =============================================================================
+
+#[test]
+fn test_record_timing_split() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = LlmSpanBuilder::new("gpt-4").build();
+        openinference_instrumentation::span_builder::record_timing_split(
+            &span,
+            std::time::Duration::from_millis(35),
+            std::time::Duration::from_millis(1_240),
+            &TraceConfig::default(),
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_i64_attribute(span, "llm.prompt_build_ms", 35);
+    assert_i64_attribute(span, "llm.inference_ms", 1_240);
+}
+
+// =============================================================================
+// Metadata tests
+// =============================================================================
+
+#[test]
+fn test_metadata_value_serializes() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = ChainSpanBuilder::new("pipeline")
+            .metadata_value(serde_json::json!({"tenant": "acme", "retries": 2}))
+            .build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    let metadata = span
+        .attributes
+        .iter()
+        .find(|kv| kv.key.as_str() == "metadata")
+        .expect("metadata attribute");
+    let parsed: serde_json::Value = serde_json::from_str(&metadata.value.as_str()).unwrap();
+    assert_eq!(parsed, serde_json::json!({"tenant": "acme", "retries": 2}));
+}
+
+// =============================================================================
+// Embedding token usage tests
+// =============================================================================
+
+#[test]
+fn test_embedding_input_tokens() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = EmbeddingSpanBuilder::new("text-embedding-3-small")
+            .text("hello world")
+            .input_tokens(2)
+            .build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_i64_attribute(span, "llm.token_count.prompt", 2);
+    assert_i64_attribute(span, "gen_ai.usage.input_tokens", 2);
+}
+
+// =============================================================================
+// Key override tests
+// =============================================================================
+
+#[test]
+fn test_key_override_renames_attribute() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let config = TraceConfig::builder()
+            .key_override("llm.model_name", "model")
+            .build();
+        let span = LlmSpanBuilder::new("gpt-4").config(config).build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_string_attribute(span, "model", "gpt-4");
+    assert_no_attribute(span, "llm.model_name");
+    assert_string_attribute(span, "gen_ai.request.model", "gpt-4");
+}
+
+#[test]
+fn test_key_override_applies_to_recorders() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let config = TraceConfig::builder()
+            .key_override("llm.token_count.prompt", "prompt_tokens")
+            .key_override("retrieval.documents.0.document.score", "top_score")
+            .build();
+        let span = LlmSpanBuilder::new("gpt-4").config(config.clone()).build();
+        openinference_instrumentation::span_builder::record_token_usage_with_config(
+            &span, 10, 5, &config,
+        );
+        openinference_instrumentation::span_builder::record_retrieval_documents(
+            &span,
+            &[Document {
+                id: Some("doc1".to_string()),
+                content: "Rust is fast.".to_string(),
+                score: Some(0.8),
+            }],
+            &config,
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_i64_attribute(span, "prompt_tokens", 10);
+    assert_no_attribute(span, "llm.token_count.prompt");
+    assert_i64_attribute(span, "llm.token_count.completion", 5);
+    assert_f64_attribute(span, "top_score", 0.8);
+    assert_no_attribute(span, "retrieval.documents.0.document.score");
+}
+
+// =============================================================================
+// Message count tests
+// =============================================================================
+
+#[test]
+fn test_message_counts_survive_redaction() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let config = TraceConfig::builder()
+            .hide_input_messages(true)
+            .hide_output_messages(true)
+            .build();
+        let span = LlmSpanBuilder::new("gpt-4")
+            .config(config.clone())
+            .input_message("system", "secret instructions")
+            .input_message("user", "secret question")
+            .build();
+        openinference_instrumentation::span_builder::record_output_message(
+            &span,
+            0,
+            "assistant",
+            "secret answer",
+            &config,
+        );
+        openinference_instrumentation::span_builder::record_output_message_count(&span, 1, &config);
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_string_attribute(span, "llm.input_messages.0.message.content", "__REDACTED__");
+    assert_i64_attribute(span, "llm.input_message_count", 2);
+    assert_i64_attribute(span, "llm.output_message_count", 1);
+}
+
+// =============================================================================
+// Tool outcome tests
+// =============================================================================
+
+#[test]
+fn test_tool_success_and_duration() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = ToolSpanBuilder::new("weather").success(false).build();
+        openinference_instrumentation::span_builder::record_tool_duration(
+            &span,
+            std::time::Duration::from_millis(420),
+            &TraceConfig::default(),
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_bool_attribute(span, "tool.success", false);
+    assert_i64_attribute(span, "tool.duration_ms", 420);
+}
+
+// =============================================================================
+// Span name tests
+// =============================================================================
+
+#[test]
+fn test_include_kind_in_span_name() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let config = TraceConfig::builder().include_kind_in_name(true).build();
+        let span = LlmSpanBuilder::new("gpt-4").config(config).build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    assert_eq!(spans[0].name, "LLM: gpt-4");
+}
+
+// =============================================================================
+// Multiple choices test
+// =============================================================================
+
+#[test]
+fn test_record_choices() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = LlmSpanBuilder::new("gpt-3.5-turbo-instruct").build();
+        openinference_instrumentation::span_builder::record_choices(
+            &span,
+            &[
+                ("Paris".to_string(), "stop".to_string()),
+                ("The capital of France is".to_string(), "length".to_string()),
+            ],
+            &TraceConfig::default(),
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_string_attribute(span, "llm.choices.0.completion.text", "Paris");
+    assert_string_attribute(span, "gen_ai.choice.0.finish_reason", "stop");
+    assert_string_attribute(
+        span,
+        "llm.choices.1.completion.text",
+        "The capital of France is",
+    );
+    assert_string_attribute(span, "gen_ai.choice.1.finish_reason", "length");
+}
+
+// =============================================================================
+// Reranker rank tests
+// =============================================================================
+
+#[test]
+fn test_reranker_output_rank_and_score() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let config = TraceConfig::default();
+        let span = RerankerSpanBuilder::new("rerank-english-v3.0")
+            .query("What is Rust?")
+            .build();
+        openinference_instrumentation::span_builder::record_reranker_output_documents(
+            &span,
+            &[
+                Document {
+                    id: Some("doc2".to_string()),
+                    content: "Rust is a systems language.".to_string(),
+                    score: Some(0.98),
+                },
+                Document {
+                    id: Some("doc1".to_string()),
+                    content: "Rust is a fungus.".to_string(),
+                    score: Some(0.12),
+                },
+            ],
+            &config,
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_f64_attribute(span, "reranker.output_documents.0.document.score", 0.98);
+    assert_i64_attribute(span, "reranker.output_documents.0.document.rank", 1);
+    assert_f64_attribute(span, "reranker.output_documents.1.document.score", 0.12);
+    assert_i64_attribute(span, "reranker.output_documents.1.document.rank", 2);
+}
+
+// =============================================================================
+// Embedding dimensions test
+// =============================================================================
+
+#[test]
+fn test_embedding_dimensions_from_vector() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let config = TraceConfig::default();
+        let span = EmbeddingSpanBuilder::new("text-embedding-3-small").build();
+        openinference_instrumentation::span_builder::record_embedding_vector(
+            &span,
+            0,
+            &vec![0.0_f32; 1536],
+            &config,
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_i64_attribute(span, "embedding.dimensions", 1536);
+}
+
+#[test]
+fn test_explicit_embedding_dimensions_not_overwritten() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let config = TraceConfig::default();
+        let span = EmbeddingSpanBuilder::new("text-embedding-3-small")
+            .dimensions(256)
+            .build();
+        openinference_instrumentation::span_builder::record_embedding_vector(
+            &span,
+            0,
+            &[0.0_f32; 4],
+            &config,
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    let recorded = span
+        .attributes
+        .iter()
+        .filter(|kv| kv.key.as_str() == "embedding.dimensions")
+        .count();
+    assert_eq!(recorded, 1);
+    assert_i64_attribute(span, "embedding.dimensions", 256);
+}
+
+// =============================================================================
+// Cost rollup test
+// =============================================================================
+
+#[test]
+fn test_sum_cost_across_spans() {
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        for cost in [0.25, 0.5, 1.25] {
+            let span = LlmSpanBuilder::new("gpt-4").build();
+            span.set_attribute("llm.cost.total", cost);
+            drop(span);
+        }
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 3);
+    assert!((openinference_instrumentation::sum_cost(&spans) - 2.0).abs() < f64::EPSILON);
+}
+
+// =============================================================================
+// Streaming test
+// =============================================================================
+
+#[test]
+fn test_llm_streaming_exported() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = LlmSpanBuilder::new("gpt-4").streaming(true).build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    assert_bool_attribute(&spans[0], "llm.is_streaming", true);
+}
+
+// =============================================================================
+// Distance metric test
+// =============================================================================
+
+#[test]
+fn test_retriever_distance_metric_exported() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = RetrieverSpanBuilder::new("vector_search")
+            .query("What is Rust?")
+            .distance_metric("cosine")
+            .build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    assert_string_attribute(&spans[0], "retrieval.distance_metric", "cosine");
+}
+
+// =============================================================================
+// Rendered prompt test
+// =============================================================================
+
+#[test]
+fn test_prompt_template_and_rendered_exported() {
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let config = TraceConfig::default();
+        let span = LlmSpanBuilder::new("gpt-4").build();
+        span.set_attribute("llm.prompt_template.template", "Summarize: {text}");
+        openinference_instrumentation::span_builder::record_rendered_prompt(
+            &span,
+            "Summarize: Rust is fast.",
+            &config,
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_string_attribute(span, "llm.prompt_template.template", "Summarize: {text}");
+    assert_string_attribute(
+        span,
+        "llm.prompt_template.rendered",
+        "Summarize: Rust is fast.",
+    );
+}
+
+// =============================================================================
+// Agent delegation test
+// =============================================================================
+
+#[test]
+fn test_agent_delegation_exported() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = AgentSpanBuilder::new("researcher")
+            .parent_agent("planner")
+            .delegated_to("web_searcher")
+            .build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_string_attribute(span, "agent.parent", "planner");
+    assert_string_attribute(span, "agent.delegated_to", "web_searcher");
+}
+
+// =============================================================================
+// Guardrail policy test
+// =============================================================================
+
+#[test]
+fn test_guardrail_policy_exported() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = GuardrailSpanBuilder::new("pii_filter")
+            .policy_name("pii-redaction")
+            .policy_version("2024-06")
+            .build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_string_attribute(span, "guardrail.policy_name", "pii-redaction");
+    assert_string_attribute(span, "guardrail.policy_version", "2024-06");
+}
+
+// =============================================================================
+// Embedding batch test
+// =============================================================================
+
+#[test]
+fn test_embeddings_batch_exported() {
+    // 100 items emit 200 attributes, above the SDK's default per-span limit.
+    let exporter = InMemorySpanExporterBuilder::new().build();
+    let provider = SdkTracerProvider::builder()
+        .with_max_attributes_per_span(512)
+        .with_simple_exporter(exporter.clone())
+        .build();
+    let subscriber = Registry::default().with(OpenTelemetryLayer::new(provider.tracer("test")));
+
+    tracing::subscriber::with_default(subscriber, || {
+        let config = TraceConfig::default();
+        let items: Vec<(String, Vec<f32>)> = (0..100)
+            .map(|i| (format!("text {i}"), vec![i as f32; 4]))
+            .collect();
+        let span = EmbeddingSpanBuilder::new("text-embedding-3-small").build();
+        openinference_instrumentation::span_builder::record_embeddings_batch(
+            &span, &items, &config,
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_i64_attribute(span, "embedding.dimensions", 4);
+    assert_string_attribute(span, "embedding.embeddings.0.embedding.text", "text 0");
+    assert_string_attribute(span, "embedding.embeddings.99.embedding.text", "text 99");
+    assert_eq!(
+        find_attribute(span, "embedding.embeddings.99.embedding.vector"),
+        Some(Value::Array(Array::F64(vec![99.0; 4])))
+    );
+}
+
+#[test]
+fn test_embeddings_batch_matches_per_item_recording() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let config = TraceConfig::builder()
+            .embedding_vector_encoding(EmbeddingVectorEncoding::Base64)
+            .build();
+        let items: Vec<(String, Vec<f32>)> = (0..12)
+            .map(|i| (format!("text {i}"), vec![i as f32 * 0.5; 3 + i % 2]))
+            .collect();
+
+        let span = EmbeddingSpanBuilder::new("text-embedding-3-small")
+            .config(config.clone())
+            .build();
+        openinference_instrumentation::span_builder::record_embeddings_batch(
+            &span, &items, &config,
+        );
+        drop(span);
+
+        let span = EmbeddingSpanBuilder::new("text-embedding-3-small")
+            .config(config.clone())
+            .texts(items.iter().map(|(text, _)| text.clone()))
+            .build();
+        for (i, (_, vector)) in items.iter().enumerate() {
+            openinference_instrumentation::span_builder::record_embedding_vector(
+                &span, i, vector, &config,
+            );
+        }
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 2);
+    let embeddings = |span: &SpanData| {
+        let mut attrs: Vec<(String, Value)> = span
+            .attributes
+            .iter()
+            .filter(|kv| kv.key.as_str().starts_with("embedding.embeddings."))
+            .map(|kv| (kv.key.to_string(), kv.value.clone()))
+            .collect();
+        attrs.sort_by(|a, b| a.0.cmp(&b.0));
+        attrs
+    };
+
+    assert_eq!(embeddings(&spans[0]).len(), 24);
+    assert_eq!(embeddings(&spans[0]), embeddings(&spans[1]));
+}
+
+// =============================================================================
+// Component name test
+// =============================================================================
+
+#[test]
+fn test_component_name_exported_on_every_span() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let config = TraceConfig::builder().component_name("billing-bot").build();
+        let chain = ChainSpanBuilder::new("pipeline")
+            .config(config.clone())
+            .build();
+        drop(chain);
+        let llm = LlmSpanBuilder::new("gpt-4").config(config).build();
+        drop(llm);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 2);
+    for span in &spans {
+        assert_string_attribute(span, "openinference.component", "billing-bot");
+    }
+}
+
+#[test]
+fn test_component_name_absent_by_default() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = LlmSpanBuilder::new("gpt-4").build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_no_attribute(&spans[0], "openinference.component");
+}
+
+// =============================================================================
+// Cache cost test
+// =============================================================================
+
+#[test]
+fn test_cache_cost_exported() {
+    use openinference_instrumentation::CacheCost;
+
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = LlmSpanBuilder::new("claude-3-5-sonnet").build();
+        openinference_instrumentation::span_builder::record_cache_cost(
+            &span,
+            &CacheCost {
+                write: 0.375,
+                read: 0.03,
+            },
+            &TraceConfig::default(),
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_f64_attribute(span, "llm.cost.prompt_details.cache_write", 0.375);
+    assert_f64_attribute(span, "llm.cost.prompt_details.cache_read", 0.03);
+}
+
+// =============================================================================
+// Model version test
+// =============================================================================
+
+#[test]
+fn test_llm_model_version_and_cutoff_exported() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = LlmSpanBuilder::new("gpt-4o")
+            .model_version("2024-08-06")
+            .knowledge_cutoff("2023-10")
+            .build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_string_attribute(span, "llm.model_version", "2024-08-06");
+    assert_string_attribute(span, "llm.knowledge_cutoff", "2023-10");
+}
+
+// =============================================================================
+// Request user test
+// =============================================================================
+
+#[test]
+fn test_llm_request_user_exported() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = LlmSpanBuilder::new("gpt-4")
+            .invocation_parameters(r#"{"temperature": 0.2}"#)
+            .request_user("user-hash-1234")
+            .build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_string_attribute(span, "llm.request_user", "user-hash-1234");
+    let params = match find_attribute(span, "llm.invocation_parameters") {
+        Some(Value::String(s)) => s.as_str().to_string(),
+        other => panic!("expected invocation parameters, got {other:?}"),
+    };
+    let params: serde_json::Value = serde_json::from_str(&params).unwrap();
+    assert_eq!(params["user"], "user-hash-1234");
+    assert_eq!(params["temperature"], 0.2);
+}
+
+// =============================================================================
+// Reranker GenAI test
+// =============================================================================
+
+#[test]
+fn test_reranker_gen_ai_attributes() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = RerankerSpanBuilder::new("rerank-english-v3.0")
+            .query("What is Rust?")
+            .input_tokens(42)
+            .build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_string_attribute(span, "gen_ai.request.model", "rerank-english-v3.0");
+    assert_i64_attribute(span, "gen_ai.usage.input_tokens", 42);
+    assert_i64_attribute(span, "llm.token_count.prompt", 42);
+}
+
+#[test]
+fn test_reranker_gen_ai_disabled() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let config = TraceConfig::builder().emit_gen_ai_attributes(false).build();
+        let span = RerankerSpanBuilder::new("rerank-english-v3.0")
+            .config(config)
+            .build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_no_attribute(&spans[0], "gen_ai.request.model");
+}
+
+// =============================================================================
+// Chat completion test
+// =============================================================================
+
+#[test]
+fn test_record_chat_completion() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let config = TraceConfig::default();
+        let span = LlmSpanBuilder::new("gpt-4o")
+            .input_message("user", "Say hi twice")
+            .build();
+        openinference_instrumentation::span_builder::record_chat_completion(
+            &span,
+            "gpt-4o-2024-08-06",
+            Some((12, 7)),
+            &[
+                ("assistant".to_string(), "Hi!".to_string()),
+                ("assistant".to_string(), "Hello!".to_string()),
+            ],
+            &["stop".to_string(), "function_call".to_string()],
+            &config,
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_string_attribute(span, "llm.output_messages.0.message.role", "assistant");
+    assert_string_attribute(span, "llm.output_messages.0.message.content", "Hi!");
+    assert_string_attribute(span, "llm.output_messages.1.message.content", "Hello!");
+    assert_i64_attribute(span, "llm.token_count.prompt", 12);
+    assert_i64_attribute(span, "llm.token_count.completion", 7);
+    assert_i64_attribute(span, "llm.token_count.total", 19);
+    assert_i64_attribute(span, "gen_ai.usage.output_tokens", 7);
+    assert_string_attribute(span, "llm.response.model", "gpt-4o-2024-08-06");
+    assert_string_attribute(span, "gen_ai.response.model", "gpt-4o-2024-08-06");
+    assert_string_attribute(span, "llm.finish_reason", "stop");
+    assert_eq!(
+        find_attribute(span, "gen_ai.response.finish_reasons"),
+        Some(Value::Array(Array::String(vec![
+            "stop".into(),
+            "tool_calls".into()
+        ])))
+    );
+}
+
+#[test]
+fn test_record_chat_completion_without_gen_ai() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let config = TraceConfig::builder().emit_gen_ai_attributes(false).build();
+        let span = LlmSpanBuilder::new("gpt-4o").config(config.clone()).build();
+        openinference_instrumentation::span_builder::record_chat_completion(
+            &span,
+            "gpt-4o-2024-08-06",
+            None,
+            &[("assistant".to_string(), "Hi!".to_string())],
+            &["length".to_string()],
+            &config,
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_string_attribute(span, "llm.response.model", "gpt-4o-2024-08-06");
+    assert_string_attribute(span, "llm.finish_reason", "length");
+    assert_bool_attribute(span, "llm.truncated", true);
+    assert_no_attribute(span, "gen_ai.response.model");
+    assert_no_attribute(span, "gen_ai.response.finish_reasons");
+}
+
+// =============================================================================
+// Tool parameter redaction tests
+// =============================================================================
+
+fn export_tool_with_parameters(config: TraceConfig) -> SpanData {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = ToolSpanBuilder::new("geocode")
+            .parameters(r#"{"address": "1 Main St"}"#)
+            .config(config)
+            .build();
+        drop(span);
+    });
+
+    exporter.get_finished_spans().unwrap().remove(0)
+}
+
+#[test]
+fn test_tool_parameters_visible_by_default() {
+    let span = export_tool_with_parameters(TraceConfig::default());
+    assert_string_attribute(&span, "tool.parameters", r#"{"address": "1 Main St"}"#);
+}
+
+#[test]
+fn test_tool_parameters_redacted_under_hide_inputs() {
+    let config = TraceConfig::builder().hide_inputs(true).build();
+    let span = export_tool_with_parameters(config);
+    assert_string_attribute(&span, "tool.parameters", "__REDACTED__");
+}
+
+#[test]
+fn test_tool_parameters_redacted_under_dedicated_flag() {
+    let config = TraceConfig::builder().hide_tool_parameters(true).build();
+    let span = export_tool_with_parameters(config);
+    assert_string_attribute(&span, "tool.parameters", "__REDACTED__");
+}
+
+// =============================================================================
+// Per-message token count test
+// =============================================================================
+
+#[test]
+fn test_message_tokens_exported() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let config = TraceConfig::default();
+        let span = LlmSpanBuilder::new("gpt-4")
+            .input_message("system", "Be brief.")
+            .input_message("user", "What is Rust?")
+            .build();
+        openinference_instrumentation::span_builder::record_message_tokens(&span, 0, 4, &config);
+        openinference_instrumentation::span_builder::record_message_tokens(&span, 1, 6, &config);
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_i64_attribute(span, "llm.input_messages.0.message.token_count", 4);
+    assert_i64_attribute(span, "llm.input_messages.1.message.token_count", 6);
+}
+
+// =============================================================================
+// Embedding provider test
+// =============================================================================
+
+#[test]
+fn test_embedding_provider_exported() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = EmbeddingSpanBuilder::new("embed-english-v3.0")
+            .provider("cohere")
+            .build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_string_attribute(span, "embedding.provider", "cohere");
+    assert_string_attribute(span, "gen_ai.provider.name", "cohere");
+}
+
+// =============================================================================
+// Retrieval document limit test
+// =============================================================================
+
+#[test]
+fn test_retrieval_documents_truncated_to_limit() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let config = TraceConfig::builder().max_documents_recorded(10).build();
+        let documents: Vec<Document> = (0..50)
+            .map(|i| Document {
+                id: Some(format!("doc{i}")),
+                content: format!("content {i}"),
+                score: None,
+            })
+            .collect();
+        let span = RetrieverSpanBuilder::new("vector_search")
+            .config(config.clone())
+            .build();
+        openinference_instrumentation::span_builder::record_retrieval_documents(
+            &span, &documents, &config,
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    let recorded = span
+        .attributes
+        .iter()
+        .filter(|kv| {
+            kv.key.as_str().starts_with("retrieval.documents.")
+                && kv.key.as_str().ends_with(".document.id")
+        })
+        .count();
+    assert_eq!(recorded, 10);
+    assert_string_attribute(span, "retrieval.documents.9.document.id", "doc9");
+    assert_no_attribute(span, "retrieval.documents.10.document.id");
+    assert_bool_attribute(span, "retrieval.documents_truncated", true);
+}
+
+// =============================================================================
+// Chain type test
+// =============================================================================
+
+#[test]
+fn test_chain_type_exported() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = ChainSpanBuilder::new("answer_question")
+            .chain_type("rag")
+            .build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    assert_string_attribute(&spans[0], "chain.type", "rag");
+}
+
+// =============================================================================
+// Structured tool call test
+// =============================================================================
+
+#[test]
+fn test_tool_call_json_exported() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let config = TraceConfig::default();
+        let span = LlmSpanBuilder::new("gpt-4").build();
+        openinference_instrumentation::span_builder::record_tool_call_json(
+            &span,
+            0,
+            0,
+            "get_weather",
+            serde_json::json!({"city": "Paris"}),
+            &config,
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_string_attribute(
+        span,
+        "llm.output_messages.0.message.tool_calls.0.tool_call.function.name",
+        "get_weather",
+    );
+    assert_string_attribute(
+        span,
+        "llm.output_messages.0.message.tool_calls.0.tool_call.function.arguments",
+        r#"{"city":"Paris"}"#,
+    );
+}
+
+#[test]
+fn test_tool_call_json_redacted_under_hide_outputs() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let config = TraceConfig::builder().hide_outputs(true).build();
+        let span = LlmSpanBuilder::new("gpt-4").build();
+        openinference_instrumentation::span_builder::record_tool_call_json(
+            &span,
+            0,
+            0,
+            "get_weather",
+            serde_json::json!({"city": "Paris"}),
+            &config,
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_string_attribute(
+        &spans[0],
+        "llm.output_messages.0.message.tool_calls.0.tool_call.function.arguments",
+        "__REDACTED__",
+    );
+}
+
+#[test]
+fn test_output_tool_call_redacted_like_tool_call_json() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let config = TraceConfig::builder().hide_outputs(true).build();
+        let span = LlmSpanBuilder::new("gpt-4").build();
+        openinference_instrumentation::span_builder::record_output_tool_call_with_config(
+            &span,
+            0,
+            0,
+            "call_1",
+            "get_weather",
+            r#"{"city":"Paris"}"#,
+            &config,
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    let span = &spans[0];
+    assert_string_attribute(
+        span,
+        "llm.output_messages.0.message.tool_calls.0.tool_call.id",
+        "call_1",
+    );
+    assert_string_attribute(
+        span,
+        "llm.output_messages.0.message.tool_calls.0.tool_call.function.name",
+        "get_weather",
+    );
+    assert_string_attribute(
+        span,
+        "llm.output_messages.0.message.tool_calls.0.tool_call.function.arguments",
+        "__REDACTED__",
+    );
+}
+
+// =============================================================================
+// Lowercase span kind test
+// =============================================================================
+
+#[test]
+fn test_lowercase_span_kind() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let config = TraceConfig::builder().lowercase_span_kind(true).build();
+        let span = LlmSpanBuilder::new("gpt-4").config(config).build();
+        drop(span);
+        let span = LlmSpanBuilder::new("gpt-4").build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 2);
+    assert_string_attribute(&spans[0], "openinference.span.kind", "llm");
+    assert_string_attribute(&spans[1], "openinference.span.kind", "LLM");
+}
+
+// =============================================================================
+// Response object test
+// =============================================================================
+
+#[test]
+fn test_response_object_and_api_version_exported() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = LlmSpanBuilder::new("gpt-4").build();
+        openinference_instrumentation::span_builder::record_response_object(
+            &span,
+            Some("chat.completion"),
+            Some("2024-10-21"),
+            &TraceConfig::default(),
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_string_attribute(span, "llm.response_object", "chat.completion");
+    assert_string_attribute(span, "llm.api_version", "2024-10-21");
+}
+
+// =============================================================================
+// Per-span GenAI override test
+// =============================================================================
+
+#[test]
+fn test_builder_emit_gen_ai_overrides_config() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let config = TraceConfig::builder().emit_gen_ai_attributes(true).build();
+        let span = LlmSpanBuilder::new("gpt-4")
+            .config(config)
+            .emit_gen_ai(false)
+            .temperature(0.5)
+            .build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_string_attribute(span, "llm.model_name", "gpt-4");
+    assert_no_attribute(span, "gen_ai.request.model");
+    assert_no_attribute(span, "gen_ai.request.temperature");
+}
+
+// =============================================================================
+// Rate limit test
+// =============================================================================
+
+#[test]
+fn test_rate_limit_retry_after_exported() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = LlmSpanBuilder::new("gpt-4").build();
+        openinference_instrumentation::span_builder::record_rate_limit(
+            &span,
+            30,
+            &TraceConfig::default(),
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_i64_attribute(span, "llm.rate_limit.retry_after", 30);
+    assert_string_attribute(span, "exception.type", "RateLimitError");
+}
+
+// =============================================================================
+// Prompt and semantic cache tests
+// =============================================================================
+
+#[test]
+fn test_llm_cache_key_and_ttl_exported() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = LlmSpanBuilder::new("claude-3-5-sonnet")
+            .cache_key("system-prompt-v2")
+            .cache_ttl_seconds(300)
+            .build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_string_attribute(span, "llm.cache.key", "system-prompt-v2");
+    assert_i64_attribute(span, "llm.cache.ttl_seconds", 300);
+}
+
+#[test]
+fn test_llm_cache_breakpoints_exported() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = LlmSpanBuilder::new("claude-3-5-sonnet")
+            .cache_breakpoints(3)
+            .build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    assert_i64_attribute(&spans[0], "llm.cache.breakpoints", 3);
+}
+
+#[test]
+fn test_llm_semantic_cache_hit_exported() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = LlmSpanBuilder::new("gpt-4")
+            .semantic_cache_hit(true)
+            .semantic_cache_similarity(0.97)
+            .build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_bool_attribute(span, "llm.semantic_cache_hit", true);
+    assert_f64_attribute(span, "llm.semantic_cache_similarity", 0.97);
+}
+
+// =============================================================================
+// Nested metadata field tests
+// =============================================================================
+
+#[test]
+fn test_metadata_fields_export_dotted_keys() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = ChainSpanBuilder::new("pipeline")
+            .metadata_field("custom.foo", "bar")
+            .metadata_field("custom.retries", 3_i64)
+            .build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_string_attribute(span, "metadata.custom.foo", "bar");
+    assert_i64_attribute(span, "metadata.custom.retries", 3);
+}
+
+// =============================================================================
+// Dropped attribute diagnostic tests
+// =============================================================================
+
+/// Layer that captures the `dropped` field of warning events.
+#[derive(Clone, Default)]
+struct DroppedWarnings(std::sync::Arc<std::sync::Mutex<Vec<u64>>>);
+
+impl<S: tracing::Subscriber> tracing_subscriber::Layer<S> for DroppedWarnings {
+    fn on_event(
+        &self,
+        event: &tracing::Event<'_>,
+        _ctx: tracing_subscriber::layer::Context<'_, S>,
+    ) {
+        struct Visitor(Option<u64>);
+        impl tracing::field::Visit for Visitor {
+            fn record_u64(&mut self, field: &tracing::field::Field, value: u64) {
+                if field.name() == "dropped" {
+                    self.0 = Some(value);
+                }
+            }
+            fn record_debug(&mut self, _: &tracing::field::Field, _: &dyn std::fmt::Debug) {}
+        }
+
+        if *event.metadata().level() == tracing::Level::WARN {
+            let mut visitor = Visitor(None);
+            event.record(&mut visitor);
+            if let Some(dropped) = visitor.0 {
+                self.0.lock().unwrap().push(dropped);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_dropped_attributes_warn_without_otel_layer() {
+    let warnings = DroppedWarnings::default();
+    let subscriber = Registry::default().with(warnings.clone());
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = ToolSpanBuilder::new("search")
+            .description("Web search")
+            .build();
+        drop(span);
+    });
+
+    // openinference.span.kind, tool.name, tool.description
+    assert_eq!(*warnings.0.lock().unwrap(), vec![3]);
+
+    // With an OTel layer active nothing is dropped.
+    let warnings = DroppedWarnings::default();
+    let (subscriber, _exporter, _provider) = setup_tracing();
+    let subscriber = subscriber.with(warnings.clone());
+    tracing::subscriber::with_default(subscriber, || {
+        drop(ToolSpanBuilder::new("search").build());
+    });
+    assert!(warnings.0.lock().unwrap().is_empty());
+}
+
+// =============================================================================
+// Streaming usage accumulation tests
+// =============================================================================
+
+#[test]
+fn test_usage_accumulator_flushes_summed_deltas() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = LlmSpanBuilder::new("gpt-4").streaming(true).build();
+        let mut usage = openinference_instrumentation::UsageAccumulator::new();
+        usage.add_delta(12, 0);
+        usage.add_delta(0, 5);
+        usage.add_delta(0, 7);
+        usage.flush(&span);
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_i64_attribute(span, "llm.token_count.prompt", 12);
+    assert_i64_attribute(span, "llm.token_count.completion", 12);
+    assert_i64_attribute(span, "llm.token_count.total", 24);
+    assert_i64_attribute(span, "gen_ai.usage.output_tokens", 12);
+}
+
+// =============================================================================
+// Generic span builder tests
+// =============================================================================
+
+#[test]
+fn test_generic_builder_guardrail_span() {
+    use openinference_instrumentation::semconv::{attributes, SpanKind};
+
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = GenericSpanBuilder::new(SpanKind::Guardrail, "pii-filter")
+            .input("My SSN is 123-45-6789")
+            .output("My SSN is [REDACTED]")
+            .attribute(attributes::guardrail::POLICY_NAME, "pii")
+            .metadata(r#"{"action":"mask"}"#)
+            .build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_eq!(span.name, "pii-filter");
+    assert_string_attribute(span, "openinference.span.kind", "GUARDRAIL");
+    assert_string_attribute(span, "input.value", "My SSN is 123-45-6789");
+    assert_string_attribute(span, "output.value", "My SSN is [REDACTED]");
+    assert_string_attribute(span, "guardrail.policy_name", "pii");
+    assert_string_attribute(span, "metadata", r#"{"action":"mask"}"#);
+}
+
+// =============================================================================
+// Effective sampling parameter tests
+// =============================================================================
+
+#[test]
+fn test_effective_params_export_separately_from_request() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let config = TraceConfig::default();
+        let span = LlmSpanBuilder::new("o1")
+            .config(config.clone())
+            .temperature(0.7)
+            .top_p(0.9)
+            .build();
+        openinference_instrumentation::span_builder::record_effective_params(
+            &span,
+            Some(1.0),
+            Some(1.0),
+            &config,
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_f64_attribute(span, "llm.response.temperature", 1.0);
+    assert_f64_attribute(span, "llm.response.top_p", 1.0);
+    assert_f64_attribute(span, "gen_ai.request.temperature", 0.7);
+    assert_f64_attribute(span, "gen_ai.request.top_p", 0.9);
+}
+
+// =============================================================================
+// Estimated usage tests
+// =============================================================================
+
+/// Counts whitespace-separated words as tokens.
+struct WordTokenizer;
+
+impl openinference_instrumentation::Tokenizer for WordTokenizer {
+    fn count_tokens(&self, text: &str) -> usize {
+        text.split_whitespace().count()
+    }
+}
+
+#[test]
+fn test_estimated_usage_records_counts_and_flag() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = LlmSpanBuilder::new("local-model").build();
+        openinference_instrumentation::span_builder::record_estimated_usage(
+            &span,
+            &WordTokenizer,
+            "What is the capital of France?",
+            "Paris.",
+            &TraceConfig::default(),
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_i64_attribute(span, "llm.token_count.prompt", 6);
+    assert_i64_attribute(span, "llm.token_count.completion", 1);
+    assert_i64_attribute(span, "llm.token_count.total", 7);
+    assert_bool_attribute(span, "llm.token_count.estimated", true);
+}
+
+// =============================================================================
+// Agent final answer and invoked tools tests
+// =============================================================================
+
+#[test]
+fn test_agent_final_answer_exports_and_redacts() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = AgentSpanBuilder::new("researcher")
+            .final_answer("The answer is 42.")
+            .build();
+        drop(span);
+
+        let config = TraceConfig::builder().hide_outputs(true).build();
+        let span = AgentSpanBuilder::new("researcher")
+            .config(config)
+            .final_answer("The answer is 42.")
+            .build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 2);
+
+    assert_string_attribute(&spans[0], "output.value", "The answer is 42.");
+    assert_bool_attribute(&spans[0], "agent.final", true);
+
+    assert_string_attribute(&spans[1], "output.value", "__REDACTED__");
+    assert_bool_attribute(&spans[1], "agent.final", true);
+}
+
+#[test]
+fn test_agent_invoked_tools_exported_as_array() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = AgentSpanBuilder::new("researcher")
+            .invoked_tools(vec!["search".into(), "calculator".into(), "search".into()])
+            .build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    assert_string_array_attribute(
+        &spans[0],
+        "agent.invoked_tools",
+        &["search", "calculator", "search"],
+    );
+}
+
+// =============================================================================
+// Retrieval query embedding tests
+// =============================================================================
+
+#[test]
+fn test_retriever_query_embedding_exports_and_redacts() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = RetrieverSpanBuilder::new("vector-search")
+            .query_embedding(vec![0.5, -0.25])
+            .build();
+        drop(span);
+
+        let config = TraceConfig::builder().hide_embeddings_vectors(true).build();
+        let span = RetrieverSpanBuilder::new("vector-search")
+            .config(config)
+            .query_embedding(vec![0.5, -0.25])
+            .build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 2);
+
+    match find_attribute(&spans[0], "retrieval.query_embedding") {
+        Some(Value::Array(Array::F64(values))) => assert_eq!(values, vec![0.5, -0.25]),
+        other => panic!("expected F64 array, got {:?}", other),
+    }
+    assert_string_attribute(&spans[1], "retrieval.query_embedding", "__REDACTED__");
+}
+
+// =============================================================================
+// Citation tests
+// =============================================================================
+
+#[test]
+fn test_citations_export_indexed_keys() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = LlmSpanBuilder::new("gpt-4").build();
+        openinference_instrumentation::span_builder::record_citations(
+            &span,
+            &[(0, "doc-17".to_string()), (2, "doc-42".to_string())],
+            &TraceConfig::default(),
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_string_attribute(span, "llm.output.citations.0.document_id", "doc-17");
+    assert_i64_attribute(span, "llm.output.citations.0.document_index", 0);
+    assert_string_attribute(span, "llm.output.citations.1.document_id", "doc-42");
+    assert_i64_attribute(span, "llm.output.citations.1.document_index", 2);
+}
+
+// =============================================================================
+// Reasoning ratio tests
+// =============================================================================
+
+#[test]
+fn test_reasoning_ratio_computed() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = LlmSpanBuilder::new("o3-mini").build();
+        openinference_instrumentation::span_builder::record_reasoning_ratio(
+            &span,
+            300,
+            400,
+            &TraceConfig::default(),
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    assert_f64_attribute(&spans[0], "llm.reasoning_ratio", 0.75);
+}
+
+// =============================================================================
+// Queue time tests
+// =============================================================================
+
+#[test]
+fn test_queue_time_exported_as_seconds() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = LlmSpanBuilder::new("llama-3-8b").build();
+        openinference_instrumentation::span_builder::record_queue_time(
+            &span,
+            std::time::Duration::from_millis(1500),
+            &TraceConfig::default(),
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    assert_f64_attribute(&spans[0], "gen_ai.server.queue_time", 1.5);
+}
+
+// =============================================================================
+// Attribute prefix tests
+// =============================================================================
+
+#[test]
+fn test_attribute_prefix_applies_to_openinference_keys_only() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let config = TraceConfig::builder().attribute_prefix("oi.").build();
+        let span = LlmSpanBuilder::new("gpt-4")
+            .config(config.clone())
+            .provider("openai")
+            .build();
+        openinference_instrumentation::span_builder::record_message_tokens(&span, 0, 12, &config);
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_string_attribute(span, "oi.llm.model_name", "gpt-4");
+    assert_string_attribute(span, "oi.llm.provider", "openai");
+    assert_i64_attribute(span, "oi.llm.input_messages.0.message.token_count", 12);
+    assert_no_attribute(span, "llm.model_name");
+    assert_string_attribute(span, "gen_ai.request.model", "gpt-4");
+}
+
+#[test]
+fn test_attribute_prefix_applies_to_recorders() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let config = TraceConfig::builder().attribute_prefix("oi.").build();
+        let span = RetrieverSpanBuilder::new("search")
+            .config(config.clone())
+            .build();
+        openinference_instrumentation::span_builder::record_retrieval_documents(
+            &span,
+            &[Document {
+                id: Some("doc1".to_string()),
+                content: "Rust is fast.".to_string(),
+                score: Some(0.9),
+            }],
+            &config,
+        );
+        openinference_instrumentation::span_builder::record_token_usage_with_config(
+            &span, 3, 2, &config,
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_string_attribute(span, "oi.retrieval.documents.0.document.id", "doc1");
+    assert_string_attribute(
+        span,
+        "oi.retrieval.documents.0.document.content",
+        "Rust is fast.",
+    );
+    assert_f64_attribute(span, "oi.retrieval.documents.0.document.score", 0.9);
+    assert_no_attribute(span, "retrieval.documents.0.document.id");
+    assert_i64_attribute(span, "oi.llm.token_count.prompt", 3);
+    assert_no_attribute(span, "llm.token_count.prompt");
+    assert_i64_attribute(span, "gen_ai.usage.input_tokens", 3);
+}
+
+#[test]
+fn test_attribute_prefix_keeps_openinference_markers() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let config = TraceConfig::builder()
+            .attribute_prefix("oi.")
+            .component_name("router")
+            .build();
+        drop(
+            ChainSpanBuilder::new("pipeline")
+                .config(config)
+                .trace_root(true)
+                .build(),
+        );
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_string_attribute(span, "openinference.span.kind", "CHAIN");
+    assert_string_attribute(span, "openinference.component", "router");
+    assert_bool_attribute(span, "openinference.trace.root", true);
+    assert_no_attribute(span, "oi.openinference.span.kind");
+}
+
+// =============================================================================
+// Tool call finish reason default tests
+// =============================================================================
+
+#[test]
+fn test_tool_call_defaults_finish_reason_to_tool_calls() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let config = TraceConfig::builder()
+            .default_tool_calls_finish_reason(true)
+            .build();
+        let span = LlmSpanBuilder::new("gpt-4").config(config.clone()).build();
+        openinference_instrumentation::span_builder::record_output_tool_call_with_config(
+            &span,
+            0,
+            0,
+            "call_1",
+            "get_weather",
+            r#"{"city":"Paris"}"#,
+            &config,
+        );
+        openinference_instrumentation::span_builder::record_output_tool_call_with_config(
+            &span,
+            0,
+            1,
+            "call_2",
+            "get_time",
+            r#"{"tz":"CET"}"#,
+            &config,
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_string_attribute(span, "llm.finish_reason", "tool_calls");
+    assert_string_array_attribute(span, "gen_ai.response.finish_reasons", &["tool_calls"]);
+    let recorded = span
+        .attributes
+        .iter()
+        .filter(|kv| kv.key.as_str() == "llm.finish_reason")
+        .count();
+    assert_eq!(recorded, 1);
+}
+
+#[test]
+fn test_tool_call_finish_reason_default_is_opt_in() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = LlmSpanBuilder::new("claude-3").build();
+        openinference_instrumentation::span_builder::record_finish_reason(
+            &span,
+            "anthropic",
+            "end_turn",
+        );
+        openinference_instrumentation::span_builder::record_output_tool_call(
+            &span,
+            0,
+            0,
+            "call_1",
+            "get_weather",
+            r#"{"city":"Paris"}"#,
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_string_attribute(span, "llm.finish_reason", "stop");
+    let recorded = span
+        .attributes
+        .iter()
+        .filter(|kv| kv.key.as_str() == "llm.finish_reason")
+        .count();
+    assert_eq!(recorded, 1);
+}
+
+// =============================================================================
+// Conversation turn count tests
+// =============================================================================
+
+#[test]
+fn test_conversation_turn_count_survives_redaction() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let config = TraceConfig::builder().hide_input_messages(true).build();
+        let span = LlmSpanBuilder::new("gpt-4")
+            .config(config)
+            .input_message("user", "Hi")
+            .input_message("assistant", "Hello!")
+            .input_message("user", "What's new?")
+            .conversation_turn_count(2)
+            .build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_i64_attribute(span, "llm.conversation.turn_count", 2);
+    assert_string_attribute(span, "llm.input_messages.2.message.content", "__REDACTED__");
+}
+
+// =============================================================================
+// Parallel tool call tests
+// =============================================================================
+
+#[test]
+fn test_parallel_tool_calls_flag_and_count() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = LlmSpanBuilder::new("gpt-4").build();
+        openinference_instrumentation::span_builder::record_output_tool_calls(
+            &span,
+            0,
+            &[
+                ("call_1", "get_weather", r#"{"city":"Paris"}"#),
+                ("call_2", "get_weather", r#"{"city":"Tokyo"}"#),
+                ("call_3", "get_time", r#"{"tz":"JST"}"#),
+            ],
+            &TraceConfig::default(),
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_bool_attribute(span, "llm.parallel_tool_calls", true);
+    assert_i64_attribute(span, "llm.parallel_tool_call_count", 3);
+    assert_string_attribute(
+        span,
+        "llm.output_messages.0.message.tool_calls.2.tool_call.function.name",
+        "get_time",
+    );
+}
+
+// =============================================================================
+// Span level tests
+// =============================================================================
+
+#[test]
+fn test_span_level_respected_by_level_filter() {
+    use tracing_subscriber::filter::LevelFilter;
+
+    let export_at = |filter: LevelFilter| {
+        let exporter = InMemorySpanExporterBuilder::new().build();
+        let provider = SdkTracerProvider::builder()
+            .with_simple_exporter(exporter.clone())
+            .build();
+        let subscriber = Registry::default()
+            .with(filter)
+            .with(OpenTelemetryLayer::new(provider.tracer("test")));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let config = TraceConfig::builder()
+                .span_level(tracing::Level::DEBUG)
+                .build();
+            drop(LlmSpanBuilder::new("gpt-4").config(config).build());
+        });
+        exporter.get_finished_spans().unwrap().len()
+    };
+
+    assert_eq!(export_at(LevelFilter::INFO), 0);
+    assert_eq!(export_at(LevelFilter::DEBUG), 1);
+}
+
+// =============================================================================
+// Input language tests
+// =============================================================================
+
+#[test]
+fn test_input_language_exported_on_llm_and_chain() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        drop(LlmSpanBuilder::new("gpt-4").language("pt-BR").build());
+        drop(ChainSpanBuilder::new("translate").language("ja").build());
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 2);
+
+    assert_string_attribute(&spans[0], "input.language", "pt-BR");
+    assert_string_attribute(&spans[1], "input.language", "ja");
+}
+
+// =============================================================================
+// Safety rating tests
+// =============================================================================
+
+#[test]
+fn test_safety_ratings_export_per_category() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = LlmSpanBuilder::new("gemini-1.5-pro").build();
+        openinference_instrumentation::span_builder::record_safety_ratings(
+            &span,
+            &[
+                (
+                    "HARM_CATEGORY_HARASSMENT".to_string(),
+                    "NEGLIGIBLE".to_string(),
+                ),
+                (
+                    "HARM_CATEGORY_DANGEROUS_CONTENT".to_string(),
+                    "LOW".to_string(),
+                ),
+            ],
+            &TraceConfig::default(),
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_string_attribute(span, "llm.safety.HARM_CATEGORY_HARASSMENT", "NEGLIGIBLE");
+    assert_string_attribute(span, "llm.safety.HARM_CATEGORY_DANGEROUS_CONTENT", "LOW");
+}
+
+// =============================================================================
+// Truncation tests
+// =============================================================================
+
+#[test]
+fn test_truncated_set_only_for_length_finish_reason() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = LlmSpanBuilder::new("claude-3-5-sonnet").build();
+        openinference_instrumentation::span_builder::record_finish_reason(
+            &span,
+            "anthropic",
+            "max_tokens",
+        );
+        drop(span);
+
+        let span = LlmSpanBuilder::new("claude-3-5-sonnet").build();
+        openinference_instrumentation::span_builder::record_finish_reason(
+            &span,
+            "anthropic",
+            "end_turn",
+        );
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 2);
+
+    assert_string_attribute(&spans[0], "llm.finish_reason", "length");
+    assert_bool_attribute(&spans[0], "llm.truncated", true);
+
+    assert_string_attribute(&spans[1], "llm.finish_reason", "stop");
+    assert_no_attribute(&spans[1], "llm.truncated");
+}
+
+// =============================================================================
+// LLM defaults tests
+// =============================================================================
+
+#[test]
+fn test_llm_defaults_apply_unless_overridden() {
+    use openinference_instrumentation::LlmDefaults;
+
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let config = TraceConfig::builder()
+            .llm_defaults(LlmDefaults {
+                provider: Some("azure".into()),
+                system: Some("openai".into()),
+                temperature: Some(0.2),
+                ..Default::default()
+            })
+            .build();
+
+        drop(LlmSpanBuilder::new("gpt-4").config(config.clone()).build());
+        drop(
+            LlmSpanBuilder::new("gpt-4")
+                .provider("openai")
+                .config(config)
+                .temperature(0.9)
+                .build(),
+        );
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 2);
+
+    assert_string_attribute(&spans[0], "llm.provider", "azure");
+    assert_string_attribute(&spans[0], "llm.system", "openai");
+    assert_f64_attribute(&spans[0], "gen_ai.request.temperature", 0.2);
+
+    assert_string_attribute(&spans[1], "llm.provider", "openai");
+    assert_string_attribute(&spans[1], "llm.system", "openai");
+    assert_f64_attribute(&spans[1], "gen_ai.request.temperature", 0.9);
+}
+
+#[test]
+fn test_llm_defaults_follow_last_config() {
+    use openinference_instrumentation::LlmDefaults;
+
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let azure = TraceConfig::builder()
+            .llm_defaults(LlmDefaults {
+                provider: Some("azure".into()),
+                temperature: Some(0.2),
+                ..Default::default()
+            })
+            .build();
+        let openai = TraceConfig::builder()
+            .llm_defaults(LlmDefaults {
+                provider: Some("openai".into()),
+                ..Default::default()
+            })
+            .build();
+
+        drop(
+            LlmSpanBuilder::new("gpt-4")
+                .config(azure)
+                .config(openai)
+                .build(),
+        );
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+
+    assert_string_attribute(&spans[0], "llm.provider", "openai");
+    assert_no_attribute(&spans[0], "gen_ai.request.temperature");
+}
+
+// =============================================================================
+// Structured output tests
+// =============================================================================
+
+#[test]
+fn test_output_schema_and_validity_export() {
+    let schema = r#"{"type":"object","properties":{"answer":{"type":"string"}}}"#;
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        // Hiding outputs does not hide the schema.
+        let config = TraceConfig::builder().hide_outputs(true).build();
+        let span = LlmSpanBuilder::new("gpt-4o")
+            .config(config.clone())
+            .output_schema(schema)
+            .build();
+        openinference_instrumentation::span_builder::record_output_valid(&span, true, &config);
+        drop(span);
+
+        let config = TraceConfig::builder().hide_output_schema(true).build();
+        let span = LlmSpanBuilder::new("gpt-4o")
+            .config(config.clone())
+            .output_schema(schema)
+            .build();
+        openinference_instrumentation::span_builder::record_output_valid(&span, false, &config);
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 2);
+
+    assert_string_attribute(&spans[0], "llm.output_schema", schema);
+    assert_bool_attribute(&spans[0], "llm.output_valid", true);
+
+    assert_string_attribute(&spans[1], "llm.output_schema", "__REDACTED__");
+    assert_bool_attribute(&spans[1], "llm.output_valid", false);
+}
+
+// =============================================================================
+// Attribute snapshot tests
+// =============================================================================
+
+#[test]
+fn test_flatten_attributes_snapshot() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = LlmSpanBuilder::new("gpt-4")
+            .provider("openai")
+            .temperature(0.5)
+            .input_message("user", "Hi")
+            .build();
+        openinference_instrumentation::span_builder::record_token_usage(&span, 3, 2);
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+
+    // Skip tracing-opentelemetry's own fields (timings, code location, thread).
+    let snapshot: Vec<(String, String)> =
+        openinference_instrumentation::flatten_attributes(&spans[0])
+            .into_iter()
+            .filter(|(key, _)| {
+                ["llm.", "gen_ai.", "openinference."]
+                    .iter()
+                    .any(|prefix| key.starts_with(prefix))
+            })
+            .collect();
+    let expected = [
+        ("gen_ai.provider.name", "openai"),
+        ("gen_ai.request.model", "gpt-4"),
+        ("gen_ai.request.temperature", "0.5"),
+        ("gen_ai.usage.input_tokens", "3"),
+        ("gen_ai.usage.output_tokens", "2"),
+        ("llm.input_message_count", "1"),
+        ("llm.input_messages.0.message.content", "Hi"),
+        ("llm.input_messages.0.message.role", "user"),
+        ("llm.model_name", "gpt-4"),
+        ("llm.provider", "openai"),
+        ("llm.token_count.completion", "2"),
+        ("llm.token_count.prompt", "3"),
+        ("llm.token_count.total", "5"),
+        ("openinference.span.kind", "LLM"),
+    ]
+    .map(|(key, value)| (key.to_string(), value.to_string()));
+    assert_eq!(snapshot, expected);
+}
+
+// =============================================================================
+// Agent span tests
+// =============================================================================
+
+#[test]
+fn test_agent_span_attributes() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = AgentSpanBuilder::new("travel-planner")
+            .description("Plans multi-city trips")
+            .build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_eq!(span.name, "agent travel-planner");
+    assert_string_attribute(span, "openinference.span.kind", "AGENT");
+    assert_string_attribute(span, "agent.name", "travel-planner");
+    assert_string_attribute(span, "gen_ai.agent.name", "travel-planner");
+    assert_string_attribute(span, "gen_ai.agent.description", "Plans multi-city trips");
+}
+
+#[test]
+fn test_agent_span_without_gen_ai_attributes() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let config = TraceConfig::builder().emit_gen_ai_attributes(false).build();
+        let span = AgentSpanBuilder::new("travel-planner")
+            .description("Plans multi-city trips")
+            .config(config)
+            .build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_string_attribute(span, "agent.name", "travel-planner");
+    assert_no_attribute(span, "gen_ai.agent.name");
+    assert_no_attribute(span, "gen_ai.agent.description");
+}
+
+#[test]
+fn test_agent_span_with_gen_ai_attributes_enabled() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let config = TraceConfig::builder().emit_gen_ai_attributes(true).build();
+        let span = AgentSpanBuilder::new("travel-planner")
+            .config(config)
+            .build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_string_attribute(span, "agent.name", "travel-planner");
+    assert_string_attribute(span, "gen_ai.agent.name", "travel-planner");
+    assert_no_attribute(span, "gen_ai.agent.description");
+}
+
+#[test]
+fn test_agent_builder_emit_gen_ai_enables_over_config() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let config = TraceConfig::builder().emit_gen_ai_attributes(false).build();
+        let span = AgentSpanBuilder::new("travel-planner")
+            .description("Plans multi-city trips")
+            .config(config)
+            .emit_gen_ai(true)
+            .build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_string_attribute(span, "agent.name", "travel-planner");
+    assert_string_attribute(span, "gen_ai.agent.name", "travel-planner");
+    assert_string_attribute(span, "gen_ai.agent.description", "Plans multi-city trips");
+}
+
+#[test]
+fn test_agent_builder_emit_gen_ai_overrides_config() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let config = TraceConfig::builder().emit_gen_ai_attributes(true).build();
+        let span = AgentSpanBuilder::new("travel-planner")
+            .description("Plans multi-city trips")
+            .config(config)
+            .emit_gen_ai(false)
+            .build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    let span = &spans[0];
+
+    assert_string_attribute(span, "agent.name", "travel-planner");
+    assert_no_attribute(span, "gen_ai.agent.name");
+    assert_no_attribute(span, "gen_ai.agent.description");
+}
+
+// =============================================================================
+// Flattened input message tests
+// =============================================================================
+
+#[test]
+fn test_flatten_messages_to_input() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let config = TraceConfig::builder()
+            .flatten_messages_to_input(true)
+            .build();
+        let span = LlmSpanBuilder::new("gpt-4")
+            .config(config.clone())
+            .input_message("system", "Be brief.")
+            .input_message("user", "Hello")
+            .build();
+        drop(span);
+
+        let span = LlmSpanBuilder::new("gpt-4")
+            .config(config)
+            .input_message("user", "Hello")
+            .input_value("explicit")
+            .build();
+        drop(span);
+
+        let span = LlmSpanBuilder::new("gpt-4")
+            .input_message("user", "Hello")
+            .build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 3);
+
+    assert_string_attribute(&spans[0], "input.value", "system: Be brief.\nuser: Hello");
+    assert_string_attribute(&spans[0], "llm.input_messages.1.message.content", "Hello");
+    assert_string_attribute(&spans[1], "input.value", "explicit");
+    assert_no_attribute(&spans[2], "input.value");
+}
+
+#[test]
+fn test_flatten_messages_to_input_redacts_with_messages() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let config = TraceConfig::builder()
+            .flatten_messages_to_input(true)
+            .hide_input_messages(true)
+            .build();
+        let span = LlmSpanBuilder::new("gpt-4")
+            .config(config)
+            .input_message("user", "secret")
+            .build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 1);
+    assert_string_attribute(&spans[0], "input.value", "__REDACTED__");
+}
+
+// =============================================================================
+// Capability flag tests
+// =============================================================================
+
+#[test]
+fn test_llm_capability_flags_reflect_builder_state() {
+    let (subscriber, exporter, _provider) = setup_tracing();
+
+    tracing::subscriber::with_default(subscriber, || {
+        let span = LlmSpanBuilder::new("gpt-4").build();
+        drop(span);
+
+        let span = LlmSpanBuilder::new("gpt-4")
+            .tool(r#"{"type": "function", "function": {"name": "search"}}"#)
+            .response_format(r#"{"type": "json_object"}"#)
+            .build();
+        drop(span);
+
+        let span = LlmSpanBuilder::new("gpt-4")
+            .output_schema(r#"{"type": "object"}"#)
+            .tool_count(0)
+            .build();
+        drop(span);
+    });
+
+    let spans = exporter.get_finished_spans().unwrap();
+    assert_eq!(spans.len(), 3);
+
+    assert_no_attribute(&spans[0], "llm.has_tools");
+    assert_no_attribute(&spans[0], "llm.has_response_format");
+
+    assert_bool_attribute(&spans[1], "llm.has_tools", true);
+    assert_bool_attribute(&spans[1], "llm.has_response_format", true);
+    assert_string_attribute(
+        &spans[1],
+        "llm.response_format",
+        r#"{"type": "json_object"}"#,
+    );
+
+    assert_bool_attribute(&spans[2], "llm.has_tools", false);
+    assert_bool_attribute(&spans[2], "llm.has_response_format", true);
+}
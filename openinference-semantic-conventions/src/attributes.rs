@@ -13,6 +13,20 @@ use opentelemetry::Key;
 /// This attribute is required for all OpenInference spans.
 pub const OPENINFERENCE_SPAN_KIND: Key = Key::from_static_str("openinference.span.kind");
 
+/// Whether the span is a replay of historical data (e.g., an eval re-run) and
+/// should be excluded from cost accounting.
+pub const OPENINFERENCE_REPLAY: Key = Key::from_static_str("openinference.replay");
+
+/// Marks the root span of a trace, for trace-level rollups.
+pub const OPENINFERENCE_TRACE_ROOT: Key = Key::from_static_str("openinference.trace.root");
+
+/// Marks the span that produced the final output of a trace.
+pub const OPENINFERENCE_TRACE_FINAL: Key = Key::from_static_str("openinference.trace.final");
+
+/// The instrumented component that emitted the span, for telling apart
+/// several components in one process.
+pub const OPENINFERENCE_COMPONENT: Key = Key::from_static_str("openinference.component");
+
 // =============================================================================
 // LLM Attributes
 // =============================================================================
@@ -30,41 +44,180 @@ pub mod llm {
     /// The LLM provider name.
     pub const PROVIDER: Key = Key::from_static_str("llm.provider");
 
+    /// The model version or snapshot, when known.
+    pub const MODEL_VERSION: Key = Key::from_static_str("llm.model_version");
+
+    /// The model's training-data knowledge cutoff, when known.
+    pub const KNOWLEDGE_CUTOFF: Key = Key::from_static_str("llm.knowledge_cutoff");
+
     /// JSON string of invocation parameters (temperature, max_tokens, etc.).
     pub const INVOCATION_PARAMETERS: Key = Key::from_static_str("llm.invocation_parameters");
 
+    /// Whether the response was requested as a stream.
+    pub const IS_STREAMING: Key = Key::from_static_str("llm.is_streaming");
+
+    /// End-user identifier sent to the provider for abuse monitoring
+    /// (distinct from the application's `user.id`).
+    pub const REQUEST_USER: Key = Key::from_static_str("llm.request_user");
+
     /// Deprecated function call (use tool_calls instead).
     pub const FUNCTION_CALL: Key = Key::from_static_str("llm.function_call");
 
+    /// The request ID returned by the provider (e.g., the `x-request-id` header).
+    pub const PROVIDER_REQUEST_ID: Key = Key::from_static_str("llm.provider_request_id");
+
+    /// The provider's response object type (e.g., "chat.completion").
+    pub const RESPONSE_OBJECT: Key = Key::from_static_str("llm.response_object");
+
+    /// The provider API version that served the request.
+    pub const API_VERSION: Key = Key::from_static_str("llm.api_version");
+
+    /// Output throughput in tokens per second.
+    pub const OUTPUT_TOKENS_PER_SECOND: Key = Key::from_static_str("llm.output_tokens_per_second");
+
+    /// Fraction of completion tokens spent on reasoning (0.0 to 1.0).
+    pub const REASONING_RATIO: Key = Key::from_static_str("llm.reasoning_ratio");
+
+    /// Time spent constructing the prompt, in milliseconds.
+    pub const PROMPT_BUILD_MS: Key = Key::from_static_str("llm.prompt_build_ms");
+
+    /// Time spent waiting on model inference, in milliseconds.
+    pub const INFERENCE_MS: Key = Key::from_static_str("llm.inference_ms");
+
+    /// Moderation category scores as a JSON object.
+    pub const MODERATION: Key = Key::from_static_str("llm.moderation");
+
+    /// Whether moderation flagged the request or response.
+    pub const FLAGGED: Key = Key::from_static_str("llm.flagged");
+
+    /// Normalized finish reason (e.g., "stop", "length", "tool_calls").
+    pub const FINISH_REASON: Key = Key::from_static_str("llm.finish_reason");
+
+    /// Finish reason exactly as reported by the provider (e.g., "end_turn").
+    pub const PROVIDER_FINISH_REASON: Key = Key::from_static_str("llm.provider_finish_reason");
+
+    /// JSON schema the structured output was requested against.
+    pub const OUTPUT_SCHEMA: Key = Key::from_static_str("llm.output_schema");
+
+    /// Response format requested from the LLM as a JSON string
+    /// (e.g., `{"type": "json_object"}`).
+    pub const RESPONSE_FORMAT: Key = Key::from_static_str("llm.response_format");
+
+    /// Whether the request set a response format or output schema.
+    pub const HAS_RESPONSE_FORMAT: Key = Key::from_static_str("llm.has_response_format");
+
+    /// Whether the structured output validated against `llm.output_schema`.
+    pub const OUTPUT_VALID: Key = Key::from_static_str("llm.output_valid");
+
+    /// Whether the response was cut off by the token limit
+    /// (finish reason `length`).
+    pub const TRUNCATED: Key = Key::from_static_str("llm.truncated");
+
+    /// Request metadata.
+    pub mod request {
+        use opentelemetry::Key;
+
+        /// When the request was sent, in unix milliseconds.
+        pub const TIMESTAMP: Key = Key::from_static_str("llm.request.timestamp");
+    }
+
+    /// Response metadata.
+    pub mod response {
+        use opentelemetry::Key;
+
+        /// The model the server reports it actually used (e.g., a dated
+        /// snapshot of the requested alias).
+        pub const MODEL: Key = Key::from_static_str("llm.response.model");
+
+        /// When the response was received, in unix milliseconds.
+        pub const TIMESTAMP: Key = Key::from_static_str("llm.response.timestamp");
+
+        /// Sampling temperature the server reports it actually used.
+        pub const TEMPERATURE: Key = Key::from_static_str("llm.response.temperature");
+
+        /// Nucleus sampling `top_p` the server reports it actually used.
+        pub const TOP_P: Key = Key::from_static_str("llm.response.top_p");
+    }
+
+    /// Rate-limit information reported by the provider.
+    pub mod rate_limit {
+        use opentelemetry::Key;
+
+        /// Requests remaining in the current rate-limit window.
+        pub const REMAINING: Key = Key::from_static_str("llm.rate_limit.remaining");
+
+        /// Seconds to wait before retrying, from the provider's `Retry-After`.
+        pub const RETRY_AFTER: Key = Key::from_static_str("llm.rate_limit.retry_after");
+    }
+
+    /// Whether the response was served from a semantic cache (e.g., by an
+    /// LLM gateway) instead of the model.
+    pub const SEMANTIC_CACHE_HIT: Key = Key::from_static_str("llm.semantic_cache_hit");
+
+    /// Similarity score between the request and the semantic cache entry.
+    pub const SEMANTIC_CACHE_SIMILARITY: Key =
+        Key::from_static_str("llm.semantic_cache_similarity");
+
+    /// Prompt-cache attributes (e.g., Anthropic cache breakpoints, OpenAI
+    /// prompt caching).
+    pub mod cache {
+        use opentelemetry::Key;
+
+        /// Key identifying the cached prompt prefix.
+        pub const KEY: Key = Key::from_static_str("llm.cache.key");
+
+        /// Time-to-live of the cache entry in seconds.
+        pub const TTL_SECONDS: Key = Key::from_static_str("llm.cache.ttl_seconds");
+
+        /// Number of cache breakpoints in the prompt (Anthropic allows up to 4).
+        pub const BREAKPOINTS: Key = Key::from_static_str("llm.cache.breakpoints");
+    }
+
+    /// Multi-turn conversation attributes.
+    pub mod conversation {
+        use opentelemetry::Key;
+
+        /// Number of prior conversation turns in context, recorded even when
+        /// message content is hidden.
+        pub const TURN_COUNT: Key = Key::from_static_str("llm.conversation.turn_count");
+    }
+
+    /// Number of input messages, recorded even when their content is hidden.
+    pub const INPUT_MESSAGE_COUNT: Key = Key::from_static_str("llm.input_message_count");
+
+    /// Number of output messages, recorded even when their content is hidden.
+    pub const OUTPUT_MESSAGE_COUNT: Key = Key::from_static_str("llm.output_message_count");
+
     /// Input messages to the LLM.
     pub mod input_messages {
         use opentelemetry::Key;
 
         /// Format: llm.input_messages.{index}.message.role
         pub fn role(index: usize) -> Key {
-            Key::from_static_str(Box::leak(
-                format!("llm.input_messages.{index}.message.role").into_boxed_str(),
-            ))
+            Key::new(format!("llm.input_messages.{index}.message.role"))
         }
 
         /// Format: llm.input_messages.{index}.message.content
         pub fn content(index: usize) -> Key {
-            Key::from_static_str(Box::leak(
-                format!("llm.input_messages.{index}.message.content").into_boxed_str(),
-            ))
+            Key::new(format!("llm.input_messages.{index}.message.content"))
+        }
+
+        /// Format: llm.input_messages.{index}.message.token_count
+        pub fn token_count(index: usize) -> Key {
+            Key::new(format!("llm.input_messages.{index}.message.token_count"))
         }
 
         /// Format: llm.input_messages.{index}.message.contents.{content_index}.message_content.type
         pub fn content_type(index: usize, content_index: usize) -> Key {
-            Key::from_static_str(Box::leak(
-                format!("llm.input_messages.{index}.message.contents.{content_index}.message_content.type").into_boxed_str(),
+            Key::new(format!(
+                "llm.input_messages.{index}.message.contents.{content_index}.message_content.type"
             ))
         }
 
         /// Format: llm.input_messages.{index}.message.contents.{content_index}.message_content.text
         pub fn content_text(index: usize, content_index: usize) -> Key {
-            Key::from_static_str(Box::leak(
-                format!("llm.input_messages.{index}.message.contents.{content_index}.message_content.text").into_boxed_str(),
+            Key::new(format!(
+                "llm.input_messages.{index}.message.contents.{content_index}.message_content.text"
             ))
         }
     }
@@ -75,16 +228,12 @@ pub mod llm {
 
         /// Format: llm.output_messages.{index}.message.role
         pub fn role(index: usize) -> Key {
-            Key::from_static_str(Box::leak(
-                format!("llm.output_messages.{index}.message.role").into_boxed_str(),
-            ))
+            Key::new(format!("llm.output_messages.{index}.message.role"))
         }
 
         /// Format: llm.output_messages.{index}.message.content
         pub fn content(index: usize) -> Key {
-            Key::from_static_str(Box::leak(
-                format!("llm.output_messages.{index}.message.content").into_boxed_str(),
-            ))
+            Key::new(format!("llm.output_messages.{index}.message.content"))
         }
 
         /// Tool calls in output messages.
@@ -93,36 +242,45 @@ pub mod llm {
 
             /// Format: llm.output_messages.{msg_index}.message.tool_calls.{call_index}.tool_call.id
             pub fn id(msg_index: usize, call_index: usize) -> Key {
-                Key::from_static_str(Box::leak(
-                    format!("llm.output_messages.{msg_index}.message.tool_calls.{call_index}.tool_call.id").into_boxed_str(),
+                Key::new(format!(
+                    "llm.output_messages.{msg_index}.message.tool_calls.{call_index}.tool_call.id"
                 ))
             }
 
             /// Format: llm.output_messages.{msg_index}.message.tool_calls.{call_index}.tool_call.function.name
             pub fn function_name(msg_index: usize, call_index: usize) -> Key {
-                Key::from_static_str(Box::leak(
-                    format!("llm.output_messages.{msg_index}.message.tool_calls.{call_index}.tool_call.function.name").into_boxed_str(),
-                ))
+                Key::new(format!("llm.output_messages.{msg_index}.message.tool_calls.{call_index}.tool_call.function.name"))
             }
 
             /// Format: llm.output_messages.{msg_index}.message.tool_calls.{call_index}.tool_call.function.arguments
             pub fn function_arguments(msg_index: usize, call_index: usize) -> Key {
-                Key::from_static_str(Box::leak(
-                    format!("llm.output_messages.{msg_index}.message.tool_calls.{call_index}.tool_call.function.arguments").into_boxed_str(),
-                ))
+                Key::new(format!("llm.output_messages.{msg_index}.message.tool_calls.{call_index}.tool_call.function.arguments"))
             }
         }
     }
 
+    /// Citations linking the output to retrieved documents.
+    pub mod citations {
+        use opentelemetry::Key;
+
+        /// Format: llm.output.citations.{index}.document_id
+        pub fn document_id(index: usize) -> Key {
+            Key::new(format!("llm.output.citations.{index}.document_id"))
+        }
+
+        /// Format: llm.output.citations.{index}.document_index
+        pub fn document_index(index: usize) -> Key {
+            Key::new(format!("llm.output.citations.{index}.document_index"))
+        }
+    }
+
     /// Prompts for text completion (non-chat).
     pub mod prompts {
         use opentelemetry::Key;
 
         /// Format: llm.prompts.{index}.prompt.text
         pub fn text(index: usize) -> Key {
-            Key::from_static_str(Box::leak(
-                format!("llm.prompts.{index}.prompt.text").into_boxed_str(),
-            ))
+            Key::new(format!("llm.prompts.{index}.prompt.text"))
         }
     }
 
@@ -132,21 +290,32 @@ pub mod llm {
 
         /// Format: llm.choices.{index}.completion.text
         pub fn text(index: usize) -> Key {
-            Key::from_static_str(Box::leak(
-                format!("llm.choices.{index}.completion.text").into_boxed_str(),
-            ))
+            Key::new(format!("llm.choices.{index}.completion.text"))
         }
     }
 
+    /// Whether any tools were offered to the LLM.
+    pub const HAS_TOOLS: Key = Key::from_static_str("llm.has_tools");
+
+    /// Number of tools offered to the LLM.
+    pub const TOOL_COUNT: Key = Key::from_static_str("llm.tool_count");
+
+    /// The tool choice mode ("auto", "none", "required", or a specific tool name).
+    pub const TOOL_CHOICE: Key = Key::from_static_str("llm.tool_choice");
+
+    /// Whether the LLM emitted more than one tool call in a single response.
+    pub const PARALLEL_TOOL_CALLS: Key = Key::from_static_str("llm.parallel_tool_calls");
+
+    /// Number of tool calls the LLM emitted in a single response.
+    pub const PARALLEL_TOOL_CALL_COUNT: Key = Key::from_static_str("llm.parallel_tool_call_count");
+
     /// Tools available to the LLM.
     pub mod tools {
         use opentelemetry::Key;
 
         /// Format: llm.tools.{index}.tool.json_schema
         pub fn json_schema(index: usize) -> Key {
-            Key::from_static_str(Box::leak(
-                format!("llm.tools.{index}.tool.json_schema").into_boxed_str(),
-            ))
+            Key::new(format!("llm.tools.{index}.tool.json_schema"))
         }
     }
 
@@ -162,6 +331,9 @@ pub mod llm {
 
         /// Version of the prompt template.
         pub const VERSION: Key = Key::from_static_str("llm.prompt_template.version");
+
+        /// The prompt produced by substituting the variables into the template.
+        pub const RENDERED: Key = Key::from_static_str("llm.prompt_template.rendered");
     }
 
     /// Token count attributes.
@@ -177,6 +349,10 @@ pub mod llm {
         /// Total number of tokens (prompt + completion).
         pub const TOTAL: Key = Key::from_static_str("llm.token_count.total");
 
+        /// Whether the counts were estimated locally rather than reported by
+        /// the provider.
+        pub const ESTIMATED: Key = Key::from_static_str("llm.token_count.estimated");
+
         /// Detailed prompt token breakdown.
         pub mod prompt_details {
             use opentelemetry::Key;
@@ -191,6 +367,12 @@ pub mod llm {
 
             /// Audio tokens in prompt.
             pub const AUDIO: Key = Key::from_static_str("llm.token_count.prompt_details.audio");
+
+            /// Text tokens in prompt.
+            pub const TEXT: Key = Key::from_static_str("llm.token_count.prompt_details.text");
+
+            /// Image tokens in prompt.
+            pub const IMAGE: Key = Key::from_static_str("llm.token_count.prompt_details.image");
         }
 
         /// Detailed completion token breakdown.
@@ -203,6 +385,32 @@ pub mod llm {
 
             /// Audio tokens in completion.
             pub const AUDIO: Key = Key::from_static_str("llm.token_count.completion_details.audio");
+
+            /// Text tokens in completion.
+            pub const TEXT: Key = Key::from_static_str("llm.token_count.completion_details.text");
+
+            /// Image tokens in completion.
+            pub const IMAGE: Key = Key::from_static_str("llm.token_count.completion_details.image");
+        }
+    }
+
+    /// Content filter results (e.g., when a response is blocked by moderation).
+    pub mod content_filter {
+        use opentelemetry::Key;
+
+        /// Format: llm.content_filter.{category}
+        pub fn category(category: &str) -> Key {
+            Key::new(format!("llm.content_filter.{category}"))
+        }
+    }
+
+    /// Per-category safety ratings (e.g., Gemini's probability labels).
+    pub mod safety {
+        use opentelemetry::Key;
+
+        /// Format: llm.safety.{category}
+        pub fn category(category: &str) -> Key {
+            Key::new(format!("llm.safety.{category}"))
         }
     }
 
@@ -255,6 +463,9 @@ pub mod embedding {
     /// The name of the embedding model.
     pub const MODEL_NAME: Key = Key::from_static_str("embedding.model_name");
 
+    /// The hosting provider of the embedding model.
+    pub const PROVIDER: Key = Key::from_static_str("embedding.provider");
+
     /// The text being embedded (single embedding).
     pub const TEXT: Key = Key::from_static_str("embedding.text");
 
@@ -264,22 +475,27 @@ pub mod embedding {
     /// JSON string of invocation parameters.
     pub const INVOCATION_PARAMETERS: Key = Key::from_static_str("embedding.invocation_parameters");
 
+    /// Encoding formats requested for the embeddings (e.g., "float", "base64").
+    pub const ENCODING_FORMATS: Key = Key::from_static_str("embedding.encoding_formats");
+
+    /// Dimensionality of the embedding vectors.
+    pub const DIMENSIONS: Key = Key::from_static_str("embedding.dimensions");
+
+    /// Distance metric the vectors are compared with (e.g., "cosine", "dot", "euclidean").
+    pub const DISTANCE_METRIC: Key = Key::from_static_str("embedding.distance_metric");
+
     /// Multiple embeddings.
     pub mod embeddings {
         use opentelemetry::Key;
 
         /// Format: embedding.embeddings.{index}.embedding.vector
         pub fn vector(index: usize) -> Key {
-            Key::from_static_str(Box::leak(
-                format!("embedding.embeddings.{index}.embedding.vector").into_boxed_str(),
-            ))
+            Key::new(format!("embedding.embeddings.{index}.embedding.vector"))
         }
 
         /// Format: embedding.embeddings.{index}.embedding.text
         pub fn text(index: usize) -> Key {
-            Key::from_static_str(Box::leak(
-                format!("embedding.embeddings.{index}.embedding.text").into_boxed_str(),
-            ))
+            Key::new(format!("embedding.embeddings.{index}.embedding.text"))
         }
     }
 }
@@ -306,6 +522,12 @@ pub mod tool {
 
     /// Tool ID.
     pub const ID: Key = Key::from_static_str("tool.id");
+
+    /// Whether the tool executed successfully.
+    pub const SUCCESS: Key = Key::from_static_str("tool.success");
+
+    /// How long the tool took to execute, in milliseconds.
+    pub const DURATION_MS: Key = Key::from_static_str("tool.duration_ms");
 }
 
 /// Attributes for tool calls.
@@ -354,36 +576,39 @@ pub mod document {
 
 /// Attributes for retriever spans.
 pub mod retrieval {
+    use opentelemetry::Key;
+
+    /// Distance metric used for similarity search (e.g., "cosine", "dot", "euclidean").
+    pub const DISTANCE_METRIC: Key = Key::from_static_str("retrieval.distance_metric");
+
+    /// Whether the recorded documents were cut short by a recording limit.
+    pub const DOCUMENTS_TRUNCATED: Key = Key::from_static_str("retrieval.documents_truncated");
+
+    /// Embedding vector of the retrieval query.
+    pub const QUERY_EMBEDDING: Key = Key::from_static_str("retrieval.query_embedding");
+
     /// Documents returned by retrieval.
     pub mod documents {
         use opentelemetry::Key;
 
         /// Format: retrieval.documents.{index}.document.id
         pub fn id(index: usize) -> Key {
-            Key::from_static_str(Box::leak(
-                format!("retrieval.documents.{index}.document.id").into_boxed_str(),
-            ))
+            Key::new(format!("retrieval.documents.{index}.document.id"))
         }
 
         /// Format: retrieval.documents.{index}.document.content
         pub fn content(index: usize) -> Key {
-            Key::from_static_str(Box::leak(
-                format!("retrieval.documents.{index}.document.content").into_boxed_str(),
-            ))
+            Key::new(format!("retrieval.documents.{index}.document.content"))
         }
 
         /// Format: retrieval.documents.{index}.document.score
         pub fn score(index: usize) -> Key {
-            Key::from_static_str(Box::leak(
-                format!("retrieval.documents.{index}.document.score").into_boxed_str(),
-            ))
+            Key::new(format!("retrieval.documents.{index}.document.score"))
         }
 
         /// Format: retrieval.documents.{index}.document.metadata
         pub fn metadata(index: usize) -> Key {
-            Key::from_static_str(Box::leak(
-                format!("retrieval.documents.{index}.document.metadata").into_boxed_str(),
-            ))
+            Key::new(format!("retrieval.documents.{index}.document.metadata"))
         }
     }
 }
@@ -410,21 +635,15 @@ pub mod reranker {
         use opentelemetry::Key;
 
         pub fn id(index: usize) -> Key {
-            Key::from_static_str(Box::leak(
-                format!("reranker.input_documents.{index}.document.id").into_boxed_str(),
-            ))
+            Key::new(format!("reranker.input_documents.{index}.document.id"))
         }
 
         pub fn content(index: usize) -> Key {
-            Key::from_static_str(Box::leak(
-                format!("reranker.input_documents.{index}.document.content").into_boxed_str(),
-            ))
+            Key::new(format!("reranker.input_documents.{index}.document.content"))
         }
 
         pub fn score(index: usize) -> Key {
-            Key::from_static_str(Box::leak(
-                format!("reranker.input_documents.{index}.document.score").into_boxed_str(),
-            ))
+            Key::new(format!("reranker.input_documents.{index}.document.score"))
         }
     }
 
@@ -433,21 +652,21 @@ pub mod reranker {
         use opentelemetry::Key;
 
         pub fn id(index: usize) -> Key {
-            Key::from_static_str(Box::leak(
-                format!("reranker.output_documents.{index}.document.id").into_boxed_str(),
-            ))
+            Key::new(format!("reranker.output_documents.{index}.document.id"))
         }
 
         pub fn content(index: usize) -> Key {
-            Key::from_static_str(Box::leak(
-                format!("reranker.output_documents.{index}.document.content").into_boxed_str(),
+            Key::new(format!(
+                "reranker.output_documents.{index}.document.content"
             ))
         }
 
         pub fn score(index: usize) -> Key {
-            Key::from_static_str(Box::leak(
-                format!("reranker.output_documents.{index}.document.score").into_boxed_str(),
-            ))
+            Key::new(format!("reranker.output_documents.{index}.document.score"))
+        }
+
+        pub fn rank(index: usize) -> Key {
+            Key::new(format!("reranker.output_documents.{index}.document.rank"))
         }
     }
 }
@@ -465,6 +684,9 @@ pub mod input {
 
     /// The MIME type of the input.
     pub const MIME_TYPE: Key = Key::from_static_str("input.mime_type");
+
+    /// Detected language or locale of the input (e.g., "en", "pt-BR").
+    pub const LANGUAGE: Key = Key::from_static_str("input.language");
 }
 
 /// Output attributes.
@@ -560,6 +782,18 @@ pub mod audio {
     pub const TRANSCRIPT: Key = Key::from_static_str("audio.transcript");
 }
 
+// =============================================================================
+// Chain Attributes
+// =============================================================================
+
+/// Chain attributes.
+pub mod chain {
+    use opentelemetry::Key;
+
+    /// The type of chain (e.g., "rag", "router", "map_reduce").
+    pub const TYPE: Key = Key::from_static_str("chain.type");
+}
+
 // =============================================================================
 // Agent/Graph Attributes
 // =============================================================================
@@ -570,6 +804,28 @@ pub mod agent {
 
     /// Agent name.
     pub const NAME: Key = Key::from_static_str("agent.name");
+
+    /// The goal the agent is working toward.
+    pub const GOAL: Key = Key::from_static_str("agent.goal");
+
+    /// The agent's current plan.
+    pub const PLAN: Key = Key::from_static_str("agent.plan");
+
+    /// Serialized agent state (e.g., memory or scratchpad).
+    pub const STATE: Key = Key::from_static_str("agent.state");
+
+    /// Name of the agent that delegated to this agent.
+    pub const PARENT: Key = Key::from_static_str("agent.parent");
+
+    /// Name of the sub-agent this agent delegated to.
+    pub const DELEGATED_TO: Key = Key::from_static_str("agent.delegated_to");
+
+    /// Whether `output.value` is the agent's final answer rather than an
+    /// intermediate output.
+    pub const FINAL: Key = Key::from_static_str("agent.final");
+
+    /// Names of the tools the agent invoked, as a string array.
+    pub const INVOKED_TOOLS: Key = Key::from_static_str("agent.invoked_tools");
 }
 
 /// Graph node attributes.
@@ -589,6 +845,21 @@ pub mod graph {
     }
 }
 
+// =============================================================================
+// Guardrail Attributes
+// =============================================================================
+
+/// Guardrail attributes.
+pub mod guardrail {
+    use opentelemetry::Key;
+
+    /// Name of the policy that evaluated the content.
+    pub const POLICY_NAME: Key = Key::from_static_str("guardrail.policy_name");
+
+    /// Version of the policy that evaluated the content.
+    pub const POLICY_VERSION: Key = Key::from_static_str("guardrail.policy_version");
+}
+
 // =============================================================================
 // Prompt Attributes
 // =============================================================================
@@ -631,3 +902,23 @@ pub mod message {
     /// Tool call ID (for tool responses).
     pub const TOOL_CALL_ID: Key = Key::from_static_str("message.tool_call_id");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_indexed_keys_are_owned() {
+        for index in 0..1000 {
+            let key = llm::input_messages::role(index);
+            assert_eq!(
+                key.as_str(),
+                format!("llm.input_messages.{index}.message.role")
+            );
+        }
+        assert_eq!(
+            llm::output_messages::tool_calls::function_name(1, 2).as_str(),
+            "llm.output_messages.1.message.tool_calls.2.tool_call.function.name"
+        );
+    }
+}
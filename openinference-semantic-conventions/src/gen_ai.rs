@@ -54,6 +54,9 @@ pub mod request {
     /// Presence penalty.
     pub const PRESENCE_PENALTY: Key = Key::from_static_str("gen_ai.request.presence_penalty");
 
+    /// Encoding formats requested for embeddings (e.g., "float", "base64").
+    pub const ENCODING_FORMATS: Key = Key::from_static_str("gen_ai.request.encoding_formats");
+
     /// Finish reasons requested.
     pub const FINISH_REASONS: Key = Key::from_static_str("gen_ai.request.finish_reasons");
 
@@ -100,6 +103,28 @@ pub mod usage {
     pub const OUTPUT_TOKENS: Key = Key::from_static_str("gen_ai.usage.output_tokens");
 }
 
+// =============================================================================
+// Server Attributes
+// =============================================================================
+
+/// Attributes identifying the inference server (useful for self-hosted models
+/// served by Ollama, vLLM, TGI, etc.).
+///
+/// The GenAI spec reuses the general OTel `server.*` attributes for these.
+pub mod server {
+    use opentelemetry::Key;
+
+    /// Server domain name or IP address.
+    pub const ADDRESS: Key = Key::from_static_str("server.address");
+
+    /// Server port number.
+    pub const PORT: Key = Key::from_static_str("server.port");
+
+    /// Time the request waited in the server's queue before inference
+    /// started, in seconds.
+    pub const QUEUE_TIME: Key = Key::from_static_str("gen_ai.server.queue_time");
+}
+
 // =============================================================================
 // Token Attributes (for events)
 // =============================================================================
@@ -125,6 +150,11 @@ pub mod choice {
 
     /// Choice index.
     pub const INDEX: Key = Key::from_static_str("gen_ai.choice.index");
+
+    /// Format: gen_ai.choice.{index}.finish_reason
+    pub fn finish_reason(index: usize) -> Key {
+        Key::new(format!("gen_ai.choice.{index}.finish_reason"))
+    }
 }
 
 // =============================================================================
@@ -204,6 +234,21 @@ pub mod events {
 
     /// Event name for assistant response.
     pub const ASSISTANT_RESPONSE: &str = "gen_ai.assistant.response";
+
+    /// Event name for a tool result message.
+    pub const TOOL_MESSAGE: &str = "gen_ai.tool.message";
+
+    /// Attribute keys carried on message events.
+    pub mod fields {
+        /// Message role (e.g., "user", "assistant").
+        pub const ROLE: &str = "role";
+
+        /// Message content.
+        pub const CONTENT: &str = "content";
+
+        /// Position of the message within the input or output list.
+        pub const INDEX: &str = "index";
+    }
 }
 
 // =============================================================================
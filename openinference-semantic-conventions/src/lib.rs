@@ -10,6 +10,7 @@
 //! ## Usage
 //!
 //! ```rust
+//! # #[cfg(feature = "otel")] {
 //! use openinference_semantic_conventions::{SpanKind, attributes};
 //! use opentelemetry::KeyValue;
 //!
@@ -19,22 +20,38 @@
 //! // Set LLM attributes
 //! let model = KeyValue::new(attributes::llm::MODEL_NAME, "gpt-4");
 //! let tokens = KeyValue::new(attributes::llm::token_count::TOTAL, 150i64);
+//! # }
 //! ```
 //!
 //! ## OTel GenAI Compatibility
 //!
 //! This crate also provides aliases for [OpenTelemetry GenAI semantic conventions](https://opentelemetry.io/docs/specs/semconv/gen-ai/)
 //! via the [`gen_ai`] module, enabling compatibility with both OpenInference and OTel backends.
+//!
+//! ## Cargo Features
+//!
+//! The default `otel` feature provides the `attributes` and `gen_ai`
+//! modules, whose keys are `opentelemetry::Key`s. The core attribute names are
+//! always available as `&'static str` constants in the [`names`] module, so
+//! with `default-features = false` the crate drops its `opentelemetry`
+//! dependency and builds as `#![no_std]`.
+
+#![cfg_attr(not(any(feature = "otel", test)), no_std)]
 
+#[cfg(feature = "otel")]
 pub mod attributes;
+#[cfg(feature = "otel")]
 pub mod gen_ai;
+pub mod names;
 mod span_kind;
 
 pub use span_kind::SpanKind;
 
 /// Re-export commonly used items
 pub mod prelude {
+    #[cfg(feature = "otel")]
     pub use crate::attributes;
+    #[cfg(feature = "otel")]
     pub use crate::gen_ai;
     pub use crate::SpanKind;
 }
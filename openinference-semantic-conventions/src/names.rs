@@ -0,0 +1,192 @@
+//! OpenInference attribute names as plain string constants.
+//!
+//! This module mirrors the most commonly used keys from the `attributes` module
+//! as `&'static str`, so they can be used without depending on
+//! `opentelemetry` (e.g., in `no_std` targets or non-OTel exporters).
+//! Build with `default-features = false`, which turns off the `otel` feature,
+//! to drop the OpenTelemetry dependency entirely.
+
+/// The kind of span. Required for all OpenInference spans.
+pub const OPENINFERENCE_SPAN_KIND: &str = "openinference.span.kind";
+
+/// Marks the span as a replay of historical data.
+pub const OPENINFERENCE_REPLAY: &str = "openinference.replay";
+
+/// Marks the root span of a trace.
+pub const OPENINFERENCE_TRACE_ROOT: &str = "openinference.trace.root";
+
+/// Marks the span that produced the final output of a trace.
+pub const OPENINFERENCE_TRACE_FINAL: &str = "openinference.trace.final";
+
+/// The instrumented component that emitted the span.
+pub const OPENINFERENCE_COMPONENT: &str = "openinference.component";
+
+/// Arbitrary metadata as a JSON string.
+pub const METADATA: &str = "metadata";
+
+/// Attribute names for Large Language Model spans.
+pub mod llm {
+    /// The name of the model being used.
+    pub const MODEL_NAME: &str = "llm.model_name";
+
+    /// The AI product as identified by the client or server instrumentation.
+    pub const SYSTEM: &str = "llm.system";
+
+    /// The hosting provider of the LLM.
+    pub const PROVIDER: &str = "llm.provider";
+
+    /// Invocation parameters passed to the LLM as a JSON string.
+    pub const INVOCATION_PARAMETERS: &str = "llm.invocation_parameters";
+
+    /// Token count attribute names.
+    pub mod token_count {
+        /// Number of tokens in the prompt.
+        pub const PROMPT: &str = "llm.token_count.prompt";
+
+        /// Number of tokens in the completion.
+        pub const COMPLETION: &str = "llm.token_count.completion";
+
+        /// Total number of tokens.
+        pub const TOTAL: &str = "llm.token_count.total";
+    }
+
+    /// Cost attribute names.
+    pub mod cost {
+        /// Cost of the prompt tokens in USD.
+        pub const PROMPT: &str = "llm.cost.prompt";
+
+        /// Cost of the completion tokens in USD.
+        pub const COMPLETION: &str = "llm.cost.completion";
+
+        /// Total cost in USD.
+        pub const TOTAL: &str = "llm.cost.total";
+    }
+}
+
+/// Attribute names for embedding spans.
+pub mod embedding {
+    /// The name of the embedding model.
+    pub const MODEL_NAME: &str = "embedding.model_name";
+}
+
+/// Attribute names for tool spans.
+pub mod tool {
+    /// The name of the tool.
+    pub const NAME: &str = "tool.name";
+
+    /// Description of the tool's purpose.
+    pub const DESCRIPTION: &str = "tool.description";
+
+    /// The parameters passed to the tool as a JSON string.
+    pub const PARAMETERS: &str = "tool.parameters";
+}
+
+/// Attribute names for agent spans.
+pub mod agent {
+    /// The name of the agent.
+    pub const NAME: &str = "agent.name";
+}
+
+/// Input attribute names.
+pub mod input {
+    /// The input value.
+    pub const VALUE: &str = "input.value";
+
+    /// The MIME type of the input value.
+    pub const MIME_TYPE: &str = "input.mime_type";
+}
+
+/// Output attribute names.
+pub mod output {
+    /// The output value.
+    pub const VALUE: &str = "output.value";
+
+    /// The MIME type of the output value.
+    pub const MIME_TYPE: &str = "output.mime_type";
+}
+
+/// Session attribute names.
+pub mod session {
+    /// Unique identifier for a session.
+    pub const ID: &str = "session.id";
+}
+
+/// User attribute names.
+pub mod user {
+    /// Unique identifier for a user.
+    pub const ID: &str = "user.id";
+}
+
+#[cfg(all(test, feature = "otel"))]
+mod tests {
+    use crate::attributes;
+
+    #[test]
+    fn test_names_match_keys() {
+        let pairs = [
+            (
+                super::OPENINFERENCE_SPAN_KIND,
+                attributes::OPENINFERENCE_SPAN_KIND,
+            ),
+            (
+                super::OPENINFERENCE_REPLAY,
+                attributes::OPENINFERENCE_REPLAY,
+            ),
+            (
+                super::OPENINFERENCE_TRACE_ROOT,
+                attributes::OPENINFERENCE_TRACE_ROOT,
+            ),
+            (
+                super::OPENINFERENCE_TRACE_FINAL,
+                attributes::OPENINFERENCE_TRACE_FINAL,
+            ),
+            (
+                super::OPENINFERENCE_COMPONENT,
+                attributes::OPENINFERENCE_COMPONENT,
+            ),
+            (super::METADATA, attributes::METADATA),
+            (super::llm::MODEL_NAME, attributes::llm::MODEL_NAME),
+            (super::llm::SYSTEM, attributes::llm::SYSTEM),
+            (super::llm::PROVIDER, attributes::llm::PROVIDER),
+            (
+                super::llm::INVOCATION_PARAMETERS,
+                attributes::llm::INVOCATION_PARAMETERS,
+            ),
+            (
+                super::llm::token_count::PROMPT,
+                attributes::llm::token_count::PROMPT,
+            ),
+            (
+                super::llm::token_count::COMPLETION,
+                attributes::llm::token_count::COMPLETION,
+            ),
+            (
+                super::llm::token_count::TOTAL,
+                attributes::llm::token_count::TOTAL,
+            ),
+            (super::llm::cost::PROMPT, attributes::llm::cost::PROMPT),
+            (
+                super::llm::cost::COMPLETION,
+                attributes::llm::cost::COMPLETION,
+            ),
+            (super::llm::cost::TOTAL, attributes::llm::cost::TOTAL),
+            (
+                super::embedding::MODEL_NAME,
+                attributes::embedding::MODEL_NAME,
+            ),
+            (super::tool::NAME, attributes::tool::NAME),
+            (super::tool::DESCRIPTION, attributes::tool::DESCRIPTION),
+            (super::tool::PARAMETERS, attributes::tool::PARAMETERS),
+            (super::agent::NAME, attributes::agent::NAME),
+            (super::input::VALUE, attributes::input::VALUE),
+            (super::input::MIME_TYPE, attributes::input::MIME_TYPE),
+            (super::output::VALUE, attributes::output::VALUE),
+            (super::output::MIME_TYPE, attributes::output::MIME_TYPE),
+            (super::session::ID, attributes::session::ID),
+            (super::user::ID, attributes::user::ID),
+        ];
+        for (name, key) in pairs {
+            assert_eq!(name, key.as_str());
+        }
+    }
+}
@@ -2,8 +2,6 @@
 //!
 //! The `openinference.span.kind` attribute is required for all OpenInference spans.
 
-use opentelemetry::Value;
-
 /// OpenInference span kinds that identify the type of operation being traced.
 ///
 /// See: <https://github.com/Arize-ai/openinference/blob/main/spec/traces.md>
@@ -69,36 +67,54 @@ impl SpanKind {
             SpanKind::Evaluator => "EVALUATOR",
         }
     }
+
+    /// Whether this kind represents a call to a model (LLM, embedding, or reranker).
+    #[inline]
+    pub const fn is_model_call(&self) -> bool {
+        matches!(
+            self,
+            SpanKind::Llm | SpanKind::Embedding | SpanKind::Reranker
+        )
+    }
+
+    /// Whether this kind represents orchestration of other steps (chain or agent).
+    #[inline]
+    pub const fn is_orchestration(&self) -> bool {
+        matches!(self, SpanKind::Chain | SpanKind::Agent)
+    }
 }
 
-impl std::str::FromStr for SpanKind {
+impl core::str::FromStr for SpanKind {
     type Err = ();
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        match s.to_uppercase().as_str() {
-            "LLM" => Ok(SpanKind::Llm),
-            "EMBEDDING" => Ok(SpanKind::Embedding),
-            "CHAIN" => Ok(SpanKind::Chain),
-            "TOOL" => Ok(SpanKind::Tool),
-            "AGENT" => Ok(SpanKind::Agent),
-            "RETRIEVER" => Ok(SpanKind::Retriever),
-            "RERANKER" => Ok(SpanKind::Reranker),
-            "GUARDRAIL" => Ok(SpanKind::Guardrail),
-            "EVALUATOR" => Ok(SpanKind::Evaluator),
-            _ => Err(()),
-        }
+        [
+            SpanKind::Llm,
+            SpanKind::Embedding,
+            SpanKind::Chain,
+            SpanKind::Tool,
+            SpanKind::Agent,
+            SpanKind::Retriever,
+            SpanKind::Reranker,
+            SpanKind::Guardrail,
+            SpanKind::Evaluator,
+        ]
+        .into_iter()
+        .find(|kind| kind.as_str().eq_ignore_ascii_case(s))
+        .ok_or(())
     }
 }
 
-impl std::fmt::Display for SpanKind {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Display for SpanKind {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.write_str(self.as_str())
     }
 }
 
-impl From<SpanKind> for Value {
+#[cfg(feature = "otel")]
+impl From<SpanKind> for opentelemetry::Value {
     fn from(kind: SpanKind) -> Self {
-        Value::String(kind.as_str().into())
+        opentelemetry::Value::String(kind.as_str().into())
     }
 }
 
@@ -132,4 +148,23 @@ mod tests {
         assert_eq!(format!("{}", SpanKind::Llm), "LLM");
         assert_eq!(format!("{}", SpanKind::Agent), "AGENT");
     }
+
+    #[test]
+    fn test_span_kind_categories() {
+        let cases = [
+            (SpanKind::Llm, true, false),
+            (SpanKind::Embedding, true, false),
+            (SpanKind::Chain, false, true),
+            (SpanKind::Tool, false, false),
+            (SpanKind::Agent, false, true),
+            (SpanKind::Retriever, false, false),
+            (SpanKind::Reranker, true, false),
+            (SpanKind::Guardrail, false, false),
+            (SpanKind::Evaluator, false, false),
+        ];
+        for (kind, model_call, orchestration) in cases {
+            assert_eq!(kind.is_model_call(), model_call, "{kind}");
+            assert_eq!(kind.is_orchestration(), orchestration, "{kind}");
+        }
+    }
 }